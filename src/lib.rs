@@ -3,6 +3,10 @@
 //! and call external solvers to solve them.
 
 pub mod lp_format;
+pub mod lp_reader;
+pub mod model;
+pub mod mps_format;
+pub mod mps_reader;
 pub mod problem;
 pub mod solvers;
 pub mod util;
@@ -1,14 +1,19 @@
 //! Traits to be implemented by structures that can be dumped in the .lp format
 //!
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::fmt::Formatter;
 use std::io::prelude::*;
-use std::io::BufWriter;
 use std::io::Result;
+use std::io::{BufWriter, Error, ErrorKind};
 
 use tempfile::NamedTempFile;
 
+use crate::lp_reader::parse_expression;
+use crate::mps_format::{write_mps, MpsFormat};
+use crate::problem::LinearExpression;
+
 /// Optimization sense
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
 pub enum LpObjective {
@@ -24,27 +29,143 @@ pub enum LpObjective {
 pub trait WriteToLpFileFormat {
     /// Write the object to the given formatter in the .lp format
     fn to_lp_file_format(&self, f: &mut fmt::Formatter) -> fmt::Result;
+    /// Write the object as the objective of a problem.
+    /// It differs from [WriteToLpFileFormat::to_lp_file_format] only for quadratic expressions,
+    /// whose quadratic part is written as `[ ... ] / 2` in objectives.
+    fn objective_to_lp_file_format(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_lp_file_format(f)
+    }
+    /// Whether the expression has quadratic terms, written between square brackets.
+    /// It is called for every row of every problem that is solved: the default implementation
+    /// writes the expression to a string to look for a `[`, so implementors should override it.
+    fn is_quadratic(&self) -> bool {
+        lp_string(self).contains('[')
+    }
 }
 
-impl<'a, T: WriteToLpFileFormat> WriteToLpFileFormat for &'a T {
+impl<T: WriteToLpFileFormat> WriteToLpFileFormat for &T {
     fn to_lp_file_format(&self, f: &mut Formatter) -> fmt::Result {
         (*self).to_lp_file_format(f)
     }
+
+    fn objective_to_lp_file_format(&self, f: &mut Formatter) -> fmt::Result {
+        (*self).objective_to_lp_file_format(f)
+    }
+
+    fn is_quadratic(&self) -> bool {
+        (*self).is_quadratic()
+    }
+}
+
+/// A number, formatted for the .lp format.
+/// Very large and very small numbers are written in scientific notation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LpNumber(pub f64);
+
+impl fmt::Display for LpNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let abs = self.0.abs();
+        if abs.is_finite() && abs != 0. && !(1e-4..1e15).contains(&abs) {
+            write!(f, "{:e}", self.0)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+struct DisplayedExpression<'a, T: ?Sized>(&'a T);
+
+impl<T: WriteToLpFileFormat + ?Sized> fmt::Display for DisplayedExpression<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.to_lp_file_format(f)
+    }
+}
+
+/// The .lp representation of an expression
+pub(crate) fn lp_string<T: WriteToLpFileFormat + ?Sized>(expr: &T) -> String {
+    DisplayedExpression(expr).to_string()
+}
+
+struct DisplayedObjective<'a, T: ?Sized>(&'a T);
+
+impl<T: WriteToLpFileFormat + ?Sized> fmt::Display for DisplayedObjective<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.objective_to_lp_file_format(f)
+    }
+}
+
+/// The .lp representation of an expression, when it is used as an objective
+pub(crate) fn objective_lp_string<T: WriteToLpFileFormat + ?Sized>(expr: &T) -> String {
+    DisplayedObjective(expr).to_string()
+}
+
+/// The values a variable can take, within its bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VariableKind {
+    /// Any real value
+    #[default]
+    Continuous,
+    /// Integer values
+    Integer,
+    /// 0 or 1. The bounds of binary variables are 0 and 1 unless specified otherwise
+    Binary,
+    /// 0, or any value between the bounds
+    SemiContinuous,
+    /// 0, or any integer between the bounds
+    SemiInteger,
+}
+
+impl VariableKind {
+    /// Whether the variable can only take integer values
+    pub fn is_integer(self) -> bool {
+        matches!(
+            self,
+            VariableKind::Integer | VariableKind::Binary | VariableKind::SemiInteger
+        )
+    }
+
+    /// Whether the variable can be 0 outside of its bounds
+    pub fn is_semi(self) -> bool {
+        matches!(
+            self,
+            VariableKind::SemiContinuous | VariableKind::SemiInteger
+        )
+    }
+}
+
+/// Integer for `true`, continuous for `false`.
+/// Code that set `is_integer: b` on a [crate::problem::Variable] can set `kind: b.into()`
+impl From<bool> for VariableKind {
+    fn from(is_integer: bool) -> Self {
+        if is_integer {
+            VariableKind::Integer
+        } else {
+            VariableKind::Continuous
+        }
+    }
 }
 
 /// A type that represents a variable. See [crate::problem::Variable].
+/// Implement at least one of [AsVariable::kind] and [AsVariable::is_integer]:
+/// each one is derived from the other by default.
 pub trait AsVariable {
     /// Variable name. Needs to be unique. See [crate::util::UniqueNameGenerator]
     fn name(&self) -> &str;
-    /// Whether the variable is forced to take only integer values
-    fn is_integer(&self) -> bool;
+    /// Whether the variable is forced to take only integer values. Derived from [AsVariable::kind] by default
+    fn is_integer(&self) -> bool {
+        self.kind().is_integer()
+    }
+    /// The kind of the variable. Integer or continuous by default, depending on [AsVariable::is_integer]
+    fn kind(&self) -> VariableKind {
+        self.is_integer().into()
+    }
     /// Minimum allowed value for the variable
     fn lower_bound(&self) -> f64;
     /// Maximum allowed value for the variable
     fn upper_bound(&self) -> f64;
 }
 
-impl<'a, T: AsVariable> AsVariable for &'a T {
+impl<T: AsVariable> AsVariable for &T {
     fn name(&self) -> &str {
         (*self).name()
     }
@@ -53,6 +174,10 @@ impl<'a, T: AsVariable> AsVariable for &'a T {
         (*self).is_integer()
     }
 
+    fn kind(&self) -> VariableKind {
+        (*self).kind()
+    }
+
     fn lower_bound(&self) -> f64 {
         (*self).lower_bound()
     }
@@ -62,7 +187,10 @@ impl<'a, T: AsVariable> AsVariable for &'a T {
     }
 }
 
-/// A constraint expressing a relation between two expressions
+/// A constraint expressing a relation between two expressions.
+/// Create it with [Constraint::new].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub struct Constraint<E> {
     /// left hand side of the constraint
     pub lhs: E,
@@ -70,6 +198,359 @@ pub struct Constraint<E> {
     pub operator: Ordering,
     /// Right-hand side of the constraint
     pub rhs: f64,
+    /// Name of the constraint in the written problem and in solver reports.
+    /// Constraints without a name are called c0, c1, ... See [constraint_names].
+    pub name: Option<String>,
+}
+
+impl<E> Constraint<E> {
+    /// The unnamed constraint `lhs operator rhs`
+    pub fn new(lhs: E, operator: Ordering, rhs: f64) -> Self {
+        Constraint {
+            lhs,
+            operator,
+            rhs,
+            name: None,
+        }
+    }
+
+    /// Give a name to the constraint
+    pub fn named(self, name: &str) -> Self {
+        Constraint {
+            name: Some(name.to_string()),
+            ..self
+        }
+    }
+
+    /// The same constraint, borrowing its expression
+    pub fn as_ref(&self) -> Constraint<&E> {
+        Constraint {
+            lhs: &self.lhs,
+            operator: self.operator,
+            rhs: self.rhs,
+            name: self.name.clone(),
+        }
+    }
+
+    /// Make the constraint apply only when the binary variable `indicator` is equal to `value`
+    pub fn when(self, indicator: &str, value: bool) -> IndicatorConstraint<E> {
+        IndicatorConstraint {
+            indicator: indicator.to_string(),
+            value,
+            constraint: self,
+        }
+    }
+}
+
+/// One of the objectives of a problem with several objectives.
+///
+/// Objectives are optimized by decreasing priority, and objectives that have the same priority
+/// are blended: the sum of their expressions multiplied by their weights is optimized.
+/// Once a priority level is optimized, the next levels may only degrade it by its tolerance:
+/// the largest of the absolute tolerance, and of the relative tolerance times its optimal value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Objective<E> {
+    /// Name of the objective. Unnamed objectives are called o0, o1, ...
+    pub name: Option<String>,
+    /// The expression to optimize, in the direction of [LpProblem::sense]
+    pub expression: E,
+    /// Objectives with a higher priority are optimized first
+    pub priority: i32,
+    /// Weight of the objective among the ones that have the same priority
+    pub weight: f64,
+    /// Absolute degradation allowed once the objective is optimized
+    pub abs_tol: f64,
+    /// Relative degradation allowed once the objective is optimized
+    pub rel_tol: f64,
+}
+
+impl<E> Objective<E> {
+    /// An objective with a weight of 1, and the default tolerances of Gurobi
+    pub fn new(expression: E, priority: i32) -> Self {
+        Objective {
+            name: None,
+            expression,
+            priority,
+            weight: 1.,
+            abs_tol: 1e-6,
+            rel_tol: 0.,
+        }
+    }
+
+    /// Give a name to the objective
+    pub fn named(self, name: &str) -> Self {
+        Objective {
+            name: Some(name.to_string()),
+            ..self
+        }
+    }
+}
+
+/// A constraint that only applies when a binary variable has a given value: `b = 1 -> x + y <= 3`
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndicatorConstraint<E> {
+    /// Name of the binary variable that controls the constraint
+    pub indicator: String,
+    /// The value of the indicator for which the constraint applies
+    pub value: bool,
+    /// The conditional constraint. Unnamed indicator constraints are called i0, i1, ...
+    pub constraint: Constraint<E>,
+}
+
+impl<E> IndicatorConstraint<E> {
+    /// Give a name to the constraint
+    pub fn named(self, name: &str) -> Self {
+        IndicatorConstraint {
+            constraint: self.constraint.named(name),
+            ..self
+        }
+    }
+}
+
+impl<E: WriteToLpFileFormat> WriteToLpFileFormat for IndicatorConstraint<E> {
+    fn to_lp_file_format(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} = {} -> ", self.indicator, u8::from(self.value))?;
+        self.constraint.to_lp_file_format(f)
+    }
+
+    fn is_quadratic(&self) -> bool {
+        self.constraint.is_quadratic()
+    }
+}
+
+/// The linear constraints that replace each indicator constraint of the problem,
+/// for solvers that do not support [LpFeature::Indicators].
+///
+/// `b = 1 -> expr <= rhs` becomes `expr + M b <= rhs + M`, where `M` is the largest value
+/// that `expr - rhs` can take within the bounds of its variables.
+/// Equalities give two constraints, `>=` first.
+pub(crate) fn big_m_constraints<'a, P: LpProblem<'a>>(
+    problem: &'a P,
+) -> std::result::Result<Vec<Vec<Constraint<LinearExpression>>>, String> {
+    let bounds: HashMap<String, (bool, f64, f64)> = problem
+        .variables()
+        .map(|v| {
+            let bounds = (v.kind().is_integer(), v.lower_bound(), v.upper_bound());
+            (v.name().to_string(), bounds)
+        })
+        .collect();
+    // variables that are not declared get the default bounds of the format
+    let bounds_of = |name: &str| {
+        bounds
+            .get(name)
+            .copied()
+            .unwrap_or((false, 0., f64::INFINITY))
+    };
+    problem
+        .indicator_constraints()
+        .map(|indicator| {
+            let b = indicator.indicator.as_str();
+            let (is_integer, low, up) = bounds_of(b);
+            if !is_integer || low < 0. || up > 1. {
+                return Err(format!("indicator variable {} must be binary", b));
+            }
+            let text = lp_string(&indicator.constraint.lhs);
+            let mut lhs = parse_expression(&text).map_err(|e| {
+                format!("invalid indicator constraint expression {:?}: {}", text, e)
+            })?;
+            let rhs = indicator.constraint.rhs - lhs.constant();
+            lhs.set_constant(0.);
+            let (mut min, mut max) = (0., 0.);
+            for (name, coef) in lhs.terms() {
+                let (_, low, up) = bounds_of(name);
+                let (a, b) = (coef * low, coef * up);
+                min += a.min(b);
+                max += a.max(b);
+            }
+            let operators: &[Ordering] = match indicator.constraint.operator {
+                Ordering::Equal => &[Ordering::Greater, Ordering::Less],
+                Ordering::Less => &[Ordering::Less],
+                Ordering::Greater => &[Ordering::Greater],
+            };
+            operators
+                .iter()
+                .map(|&operator| {
+                    // how much the constraint must be relaxed when it does not apply
+                    let m = match operator {
+                        Ordering::Less => max - rhs,
+                        _ => rhs - min,
+                    };
+                    if !m.is_finite() {
+                        return Err(format!(
+                            "cannot compute a big-M value for the indicator constraint on {}: \
+                             {} is unbounded",
+                            b, text
+                        ));
+                    }
+                    let m = m.max(0.);
+                    let sign = if operator == Ordering::Less { 1. } else { -1. };
+                    let mut row = lhs.clone();
+                    let (coefficient, relaxed_rhs) = if indicator.value {
+                        (sign * m, rhs + sign * m)
+                    } else {
+                        (-sign * m, rhs)
+                    };
+                    row.add_term(b, coefficient);
+                    Ok(Constraint {
+                        lhs: row,
+                        operator,
+                        rhs: relaxed_rhs,
+                        name: None,
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The names under which the constraints of a problem are written, in order.
+///
+/// Characters that are not allowed in the .lp format are replaced by `_`.
+/// Constraints without a name are called `c` followed by their index,
+/// and a suffix is added to names that are already used.
+///
+/// ```
+/// use lp_solvers::lp_format::constraint_names;
+/// use lp_solvers::model::ProblemBuilder;
+///
+/// let mut builder = ProblemBuilder::new("names");
+/// let x = builder.continuous("x", 0., 1.);
+/// builder.add_constraint(x.leq(1.).named("max capacity"));
+/// builder.add_constraint(x.geq(0.));
+/// builder.add_constraint(x.leq(2.).named("c1"));
+/// builder.add_constraint(x.leq(3.).named("max capacity"));
+/// let names = constraint_names(&builder.build());
+/// assert_eq!(names, vec!["max_capacity", "c1_2", "c1", "max_capacity_2"]);
+/// ```
+pub fn constraint_names<'a, P: LpProblem<'a>>(problem: &'a P) -> Vec<String> {
+    let mut names = row_names(problem, LpFeature::ALL);
+    names.truncate(problem.constraints().count());
+    names
+}
+
+/// The names of all the rows of the problem, in the order they are written:
+/// constraints, then range constraints, indicator constraints, lazy constraints and user cuts.
+/// Without [LpFeature::Ranges], two-sided ranges are split in rows suffixed with `_lo` and `_hi`,
+/// and so are equality indicator constraints without [LpFeature::Indicators].
+pub(crate) fn row_names<'a, P: LpProblem<'a>>(
+    problem: &'a P,
+    features: &[LpFeature],
+) -> Vec<String> {
+    // (explicit name, default name)
+    let mut rows: Vec<(Option<String>, String)> = problem
+        .constraints()
+        .enumerate()
+        .map(|(idx, c)| (c.name.map(|n| valid_name(&n)), format!("c{}", idx)))
+        .collect();
+    for (idx, range) in problem.ranges().enumerate() {
+        let name = range.name.as_deref().map(valid_name);
+        let default = format!("r{}", idx);
+        if range.single_side().is_some() || features.contains(&LpFeature::Ranges) {
+            rows.push((name, default));
+        } else {
+            for suffix in ["_lo", "_hi"] {
+                rows.push((name.clone().map(|n| n + suffix), default.clone() + suffix));
+            }
+        }
+    }
+    for (idx, indicator) in problem.indicator_constraints().enumerate() {
+        let name = indicator.constraint.name.as_deref().map(valid_name);
+        let default = format!("i{}", idx);
+        if indicator.constraint.operator != Ordering::Equal
+            || features.contains(&LpFeature::Indicators)
+        {
+            rows.push((name, default));
+        } else {
+            for suffix in ["_lo", "_hi"] {
+                rows.push((name.clone().map(|n| n + suffix), default.clone() + suffix));
+            }
+        }
+    }
+    for (idx, c) in problem.lazy_constraints().enumerate() {
+        rows.push((c.name.map(|n| valid_name(&n)), format!("l{}", idx)));
+    }
+    for (idx, c) in problem.user_cuts().enumerate() {
+        rows.push((c.name.map(|n| valid_name(&n)), format!("u{}", idx)));
+    }
+    // the objective row is called obj
+    unique_names(rows, &["obj"])
+}
+
+/// The names of the special ordered sets of the problem. Unnamed sets are called s0, s1, ...
+pub(crate) fn sos_names<'a, P: LpProblem<'a>>(problem: &'a P) -> Vec<String> {
+    let sets = problem
+        .sos_constraints()
+        .enumerate()
+        .map(|(idx, s)| (s.name.as_deref().map(valid_name), format!("s{}", idx)))
+        .collect();
+    unique_names(sets, &[])
+}
+
+/// The names of the objectives of the problem. Unnamed ones are called o0, o1, ...
+fn objective_names<'a, P: LpProblem<'a>>(problem: &'a P) -> Vec<String> {
+    let objectives = problem
+        .objectives()
+        .enumerate()
+        .map(|(idx, o)| (o.name.as_deref().map(valid_name), format!("o{}", idx)))
+        .collect();
+    unique_names(objectives, &[])
+}
+
+/// The names of the general constraints of the problem. Unnamed ones are called g0, g1, ...
+fn general_constraint_names<'a, P: LpProblem<'a>>(problem: &'a P) -> Vec<String> {
+    let constraints = problem
+        .general_constraints()
+        .enumerate()
+        .map(|(idx, c)| (c.name.as_deref().map(valid_name), format!("g{}", idx)))
+        .collect();
+    unique_names(constraints, &[])
+}
+
+/// Give a unique name to every (explicit name, default name) pair
+fn unique_names(names: Vec<(Option<String>, String)>, reserved: &[&str]) -> Vec<String> {
+    let mut used: HashSet<String> = reserved.iter().map(|n| n.to_string()).collect();
+    // explicit names are reserved first, so they are kept when possible
+    let explicit: Vec<_> = names
+        .into_iter()
+        .map(|(name, default)| (name.map(|n| unique_name(n, &mut used)), default))
+        .collect();
+    explicit
+        .into_iter()
+        .map(|(name, default)| name.unwrap_or_else(|| unique_name(default, &mut used)))
+        .collect()
+}
+
+/// Names may contain letters, digits and some symbols, and may not start with a digit or a period
+fn valid_name(name: &str) -> String {
+    const SYMBOLS: &str = "!\"#$%&()/,.;?@_`'{}|~";
+    let mut valid: String = name
+        .chars()
+        .take(255)
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || SYMBOLS.contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if valid.is_empty() || valid.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        valid.insert(0, 'c');
+    }
+    valid
+}
+
+fn unique_name(name: String, used: &mut HashSet<String>) -> String {
+    let name = if used.contains(&name) {
+        (2..)
+            .map(|n| format!("{}_{}", name, n))
+            .find(|n| !used.contains(n))
+            .expect("unbounded range")
+    } else {
+        name
+    };
+    used.insert(name.clone());
+    name
 }
 
 impl<E: WriteToLpFileFormat> WriteToLpFileFormat for Constraint<E> {
@@ -83,9 +564,275 @@ impl<E: WriteToLpFileFormat> WriteToLpFileFormat for Constraint<E> {
                 Ordering::Less => "<=",
                 Ordering::Greater => ">=",
             },
-            self.rhs
+            LpNumber(self.rhs)
         )
     }
+
+    fn is_quadratic(&self) -> bool {
+        self.lhs.is_quadratic()
+    }
+}
+
+/// A constraint that keeps an expression between two values: `lower <= lhs <= upper`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RangeConstraint<E> {
+    /// the constrained expression
+    pub lhs: E,
+    /// -INFINITY if there is no lower bound
+    pub lower: f64,
+    /// INFINITY if there is no upper bound
+    pub upper: f64,
+    /// Name of the constraint. Unnamed range constraints are called r0, r1, ...
+    pub name: Option<String>,
+}
+
+impl<E> RangeConstraint<E> {
+    /// Give a name to the constraint
+    pub fn named(self, name: &str) -> Self {
+        RangeConstraint {
+            name: Some(name.to_string()),
+            ..self
+        }
+    }
+
+    /// The equivalent one-sided constraint, if there is one
+    pub(crate) fn single_side(&self) -> Option<(Ordering, f64)> {
+        if self.lower == self.upper {
+            Some((Ordering::Equal, self.lower))
+        } else if self.lower == f64::NEG_INFINITY {
+            Some((Ordering::Less, self.upper))
+        } else if self.upper == f64::INFINITY {
+            Some((Ordering::Greater, self.lower))
+        } else {
+            None
+        }
+    }
+}
+
+/// The type of a special ordered set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SosType {
+    /// At most one variable of the set can be non-zero
+    S1,
+    /// At most two variables of the set can be non-zero, and they must be consecutive
+    S2,
+}
+
+/// A special ordered set: a list of variables, of which only a few can be non-zero
+#[derive(Debug, Clone, PartialEq)]
+pub struct SosConstraint {
+    /// Name of the set. Unnamed sets are called s0, s1, ...
+    pub name: Option<String>,
+    /// Type 1 or 2
+    pub kind: SosType,
+    /// (variable name, weight) pairs. The weights define the order of the variables.
+    pub variables: Vec<(String, f64)>,
+    /// Branching priority of the set.
+    /// The .lp format cannot express it, so it is only written in the MPS format.
+    pub priority: Option<u32>,
+}
+
+/// The function that defines the value of the resultant variable of a [GeneralConstraint]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeneralFunction {
+    /// The smallest of the variables, and of the constant if there is one
+    Min {
+        /// names of the variables
+        variables: Vec<String>,
+        /// an optional constant operand
+        constant: Option<f64>,
+    },
+    /// The largest of the variables, and of the constant if there is one
+    Max {
+        /// names of the variables
+        variables: Vec<String>,
+        /// an optional constant operand
+        constant: Option<f64>,
+    },
+    /// The absolute value of a variable
+    Abs(String),
+    /// 1 if all the binary variables are 1, 0 otherwise
+    And(Vec<String>),
+    /// 1 if any of the binary variables is 1, 0 otherwise
+    Or(Vec<String>),
+    /// A piecewise-linear function of a variable
+    Pwl {
+        /// name of the argument of the function
+        variable: String,
+        /// (x, y) breakpoints of the function, sorted by x
+        points: Vec<(f64, f64)>,
+    },
+}
+
+/// A constraint that sets a variable to a function of other variables: `r = MAX ( x , y , 2 )`.
+/// Only Gurobi supports them, in its `General Constraints` section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneralConstraint {
+    /// Name of the constraint. Unnamed general constraints are called g0, g1, ...
+    pub name: Option<String>,
+    /// Name of the variable that takes the value of the function
+    pub resultant: String,
+    /// The function of the other variables
+    pub function: GeneralFunction,
+}
+
+impl GeneralConstraint {
+    /// `resultant = function`
+    pub fn new(resultant: &str, function: GeneralFunction) -> Self {
+        GeneralConstraint {
+            name: None,
+            resultant: resultant.to_string(),
+            function,
+        }
+    }
+
+    /// Give a name to the constraint
+    pub fn named(self, name: &str) -> Self {
+        GeneralConstraint {
+            name: Some(name.to_string()),
+            ..self
+        }
+    }
+}
+
+impl WriteToLpFileFormat for GeneralConstraint {
+    fn to_lp_file_format(&self, f: &mut Formatter) -> fmt::Result {
+        let (function, variables, constant) = match &self.function {
+            GeneralFunction::Min {
+                variables,
+                constant,
+            } => ("MIN", variables.as_slice(), *constant),
+            GeneralFunction::Max {
+                variables,
+                constant,
+            } => ("MAX", variables.as_slice(), *constant),
+            GeneralFunction::Abs(variable) => ("ABS", std::slice::from_ref(variable), None),
+            GeneralFunction::And(variables) => ("AND", variables.as_slice(), None),
+            GeneralFunction::Or(variables) => ("OR", variables.as_slice(), None),
+            GeneralFunction::Pwl { variable, .. } => ("PWL", std::slice::from_ref(variable), None),
+        };
+        let mut operands: Vec<String> = variables.to_vec();
+        operands.extend(constant.map(|c| LpNumber(c).to_string()));
+        write!(
+            f,
+            "{} = {} ( {} )",
+            self.resultant,
+            function,
+            operands.join(" , ")
+        )?;
+        if let GeneralFunction::Pwl { points, .. } = &self.function {
+            write!(f, " :")?;
+            for (x, y) in points {
+                write!(f, " ( {} , {} )", LpNumber(*x), LpNumber(*y))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_quadratic(&self) -> bool {
+        false
+    }
+}
+
+/// Parts of the .lp format that not every solver understands.
+/// See [crate::solvers::SolverProgram::supported_features].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LpFeature {
+    /// Two-sided constraints: `lower <= expr <= upper`.
+    /// Without it, ranges are written as two constraints.
+    Ranges,
+    /// Objectives with a quadratic part: `[ ... ] / 2`
+    QuadraticObjective,
+    /// Constraints with a quadratic part: `[ ... ] <= rhs`
+    QuadraticConstraints,
+    /// Special ordered sets, in a `SOS` section
+    Sos,
+    /// Semi-continuous and semi-integer variables, in a `Semi-continuous` section
+    SemiContinuous,
+    /// Indicator constraints: `b = 1 -> expr <= rhs`.
+    /// Without it, they are rewritten as big-M constraints, using the bounds of the variables.
+    Indicators,
+    /// Gurobi's general constraints, in a `General Constraints` section
+    GeneralConstraints,
+    /// Constraints that are only checked when a solution is found, in a `Lazy Constraints` section.
+    /// Without it, they are written as ordinary constraints.
+    LazyConstraints,
+    /// Constraints that only cut off fractional solutions, in a `User Cuts` section.
+    /// Without it, they are written as ordinary constraints.
+    UserCuts,
+    /// A constant in the objective: `obj: x + 5`.
+    /// Without it, the constant is the coefficient of a variable fixed to 1,
+    /// which is removed from the solutions.
+    ObjectiveConstant,
+    /// Several objectives, in a `multi-objectives` section.
+    /// Without it, the solvers optimize the objectives one priority level at a time.
+    MultiObjective,
+}
+
+impl LpFeature {
+    /// All the features
+    pub const ALL: &'static [LpFeature] = &[
+        LpFeature::Ranges,
+        LpFeature::QuadraticObjective,
+        LpFeature::QuadraticConstraints,
+        LpFeature::Sos,
+        LpFeature::SemiContinuous,
+        LpFeature::Indicators,
+        LpFeature::GeneralConstraints,
+        LpFeature::LazyConstraints,
+        LpFeature::UserCuts,
+        LpFeature::ObjectiveConstant,
+        LpFeature::MultiObjective,
+    ];
+}
+
+impl fmt::Display for LpFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LpFeature::Ranges => "range constraints",
+            LpFeature::QuadraticObjective => "quadratic objectives",
+            LpFeature::QuadraticConstraints => "quadratic constraints",
+            LpFeature::Sos => "special ordered sets",
+            LpFeature::SemiContinuous => "semi-continuous variables",
+            LpFeature::Indicators => "indicator constraints",
+            LpFeature::GeneralConstraints => "general constraints",
+            LpFeature::LazyConstraints => "lazy constraints",
+            LpFeature::UserCuts => "user cuts",
+            LpFeature::ObjectiveConstant => "objective constants",
+            LpFeature::MultiObjective => "multiple objectives",
+        })
+    }
+}
+
+/// The features used by the problem that cannot be rewritten for solvers that lack them
+pub fn required_features<'a, P: LpProblem<'a>>(problem: &'a P) -> Vec<LpFeature> {
+    let mut features = vec![];
+    if problem.objective().is_quadratic() {
+        features.push(LpFeature::QuadraticObjective);
+    }
+    if problem.constraints().any(|c| c.lhs.is_quadratic())
+        || problem.ranges().any(|r| r.lhs.is_quadratic())
+        || problem
+            .indicator_constraints()
+            .any(|i| i.constraint.lhs.is_quadratic())
+        || problem
+            .lazy_constraints()
+            .chain(problem.user_cuts())
+            .any(|c| c.lhs.is_quadratic())
+    {
+        features.push(LpFeature::QuadraticConstraints);
+    }
+    if problem.sos_constraints().next().is_some() {
+        features.push(LpFeature::Sos);
+    }
+    if problem.variables().any(|v| v.kind().is_semi()) {
+        features.push(LpFeature::SemiContinuous);
+    }
+    if problem.general_constraints().next().is_some() {
+        features.push(LpFeature::GeneralConstraints);
+    }
+    features
 }
 
 /// Implemented by type that can be formatted as an lp problem
@@ -111,17 +858,49 @@ pub trait LpProblem<'a>: Sized {
     fn sense(&'a self) -> LpObjective;
     /// List of constraints to apply
     fn constraints(&'a self) -> Self::ConstraintIterator;
+    /// Two-sided constraints. None by default
+    fn ranges(&'a self) -> Box<dyn Iterator<Item = RangeConstraint<Self::Expression>> + 'a> {
+        Box::new(std::iter::empty())
+    }
+    /// Special ordered sets. None by default
+    fn sos_constraints(&'a self) -> Box<dyn Iterator<Item = SosConstraint> + 'a> {
+        Box::new(std::iter::empty())
+    }
+    /// Constraints that the solver only checks when it finds a solution. None by default
+    fn lazy_constraints(&'a self) -> Box<dyn Iterator<Item = Constraint<Self::Expression>> + 'a> {
+        Box::new(std::iter::empty())
+    }
+    /// Constraints that are implied by the others, but help cut off fractional solutions.
+    /// None by default
+    fn user_cuts(&'a self) -> Box<dyn Iterator<Item = Constraint<Self::Expression>> + 'a> {
+        Box::new(std::iter::empty())
+    }
+    /// A constant added to the objective. 0 by default
+    fn objective_offset(&self) -> f64 {
+        0.
+    }
+    /// Objectives that replace [LpProblem::objective] in problems with several objectives.
+    /// None by default
+    fn objectives(&'a self) -> Box<dyn Iterator<Item = Objective<Self::Expression>> + 'a> {
+        Box::new(std::iter::empty())
+    }
+    /// Constraints that set a variable to a function of other variables. None by default
+    fn general_constraints(&'a self) -> Box<dyn Iterator<Item = GeneralConstraint> + 'a> {
+        Box::new(std::iter::empty())
+    }
+    /// Constraints that only apply when a binary variable has a given value. None by default
+    fn indicator_constraints(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = IndicatorConstraint<Self::Expression>> + 'a> {
+        Box::new(std::iter::empty())
+    }
     /// Write the problem in the lp file format to the given formatter
     fn to_lp_file_format(&'a self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "\\ {}\n\n", self.name())?;
-        objective_lp_file_block(self, f)?;
-        write_constraints_lp_file_block(self, f)?;
-        write_bounds_lp_file_block(self, f)?;
-        write!(f, "\nEnd\n")?;
-        Ok(())
+        // the indicator constraints are written as they are: there are no big-M rows
+        write_lp_file(self, LpFeature::ALL, &[], f)
     }
     /// Return an object whose [fmt::Display] implementation is the problem in the .lp format
-    fn display_lp(&'a self) -> DisplayedLp<'_, Self>
+    fn display_lp(&'a self) -> DisplayedLp<'a, Self>
     where
         Self: Sized,
     {
@@ -133,24 +912,100 @@ pub trait LpProblem<'a>: Sized {
     where
         Self: Sized,
     {
-        let mut f = tempfile::Builder::new()
-            .prefix(self.name())
-            .suffix(".lp")
-            .tempfile()?;
+        tmp_file(self.name(), ".lp", |out| {
+            write!(out, "{}", self.display_lp())
+        })
+    }
+
+    /// Write the problem to a temporary .lp file, using only the given features of the format.
+    /// Parts of the problem that use other features are rewritten when possible,
+    /// and an error of kind [ErrorKind::InvalidInput] is returned when they cannot be.
+    fn to_tmp_file_with_features(&'a self, features: &[LpFeature]) -> Result<NamedTempFile>
+    where
+        Self: Sized,
+    {
+        let lp = LpWithFeatures::new(self, features)
+            .map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+        tmp_file(self.name(), ".lp", |out| write!(out, "{}", lp))
+    }
+
+    /// Write the problem to a temporary file in the MPS format. See [crate::mps_format].
+    fn to_tmp_mps_file(&'a self, format: MpsFormat) -> Result<NamedTempFile>
+    where
+        Self: Sized,
+    {
+        tmp_file(self.name(), ".mps", |out| write_mps(self, format, out))
+    }
+}
+
+fn tmp_file(
+    prefix: &str,
+    suffix: &str,
+    write: impl FnOnce(&mut BufWriter<&mut NamedTempFile>) -> Result<()>,
+) -> Result<NamedTempFile> {
+    let mut f = tempfile::Builder::new()
+        .prefix(prefix)
+        .suffix(suffix)
+        .tempfile()?;
 
-        // Use a buffered writer to limit the number of syscalls
-        let mut buf_f = BufWriter::new(&mut f);
-        write!(buf_f, "{}", self.display_lp())?;
-        buf_f.flush()?;
+    // Use a buffered writer to limit the number of syscalls
+    let mut buf_f = BufWriter::new(&mut f);
+    write(&mut buf_f)?;
+    buf_f.flush()?;
 
-        // need to explicitly drop the buffered writer here,
-        // since it holds a reference to the actual file
-        drop(buf_f);
+    // need to explicitly drop the buffered writer here,
+    // since it holds a reference to the actual file
+    drop(buf_f);
 
-        Ok(f)
+    Ok(f)
+}
+
+/// A problem written with only some features of the format.
+/// The rows that replace its indicator constraints are computed before writing,
+/// so that formatting it cannot fail.
+struct LpWithFeatures<'a, 'f, P> {
+    problem: &'a P,
+    features: &'f [LpFeature],
+    big_m: Vec<Vec<Constraint<LinearExpression>>>,
+}
+
+impl<'a, 'f, P: LpProblem<'a>> LpWithFeatures<'a, 'f, P> {
+    fn new(problem: &'a P, features: &'f [LpFeature]) -> std::result::Result<Self, String> {
+        let big_m = if features.contains(&LpFeature::Indicators) {
+            vec![]
+        } else {
+            big_m_constraints(problem)?
+        };
+        Ok(LpWithFeatures {
+            problem,
+            features,
+            big_m,
+        })
     }
 }
 
+impl<'a, P: LpProblem<'a>> fmt::Display for LpWithFeatures<'a, '_, P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write_lp_file(self.problem, self.features, &self.big_m, f)
+    }
+}
+
+fn write_lp_file<'a, P: LpProblem<'a>>(
+    prob: &'a P,
+    features: &[LpFeature],
+    big_m: &[Vec<Constraint<LinearExpression>>],
+    f: &mut Formatter,
+) -> fmt::Result {
+    write!(f, "\\ {}\n\n", prob.name())?;
+    objective_lp_file_block(prob, features, f)?;
+    write_constraints_lp_file_block(prob, features, big_m, f)?;
+    write_bounds_lp_file_block(prob, features, f)?;
+    write_sos_lp_file_block(prob, f)?;
+    write_general_constraints_lp_file_block(prob, f)?;
+    write!(f, "\nEnd\n")?;
+    Ok(())
+}
+
 /// A problem whose `Display` implementation outputs valid .lp syntax
 pub struct DisplayedLp<'a, P>(&'a P);
 
@@ -162,59 +1017,253 @@ impl<'a, P: LpProblem<'a>> std::fmt::Display for DisplayedLp<'a, P> {
 
 fn objective_lp_file_block<'a>(
     prob: &'a impl LpProblem<'a>,
+    features: &[LpFeature],
     f: &mut std::fmt::Formatter,
 ) -> std::fmt::Result {
     // Write objectives
     let obj_type = match prob.sense() {
-        LpObjective::Maximize => "Maximize\n  ",
-        LpObjective::Minimize => "Minimize\n  ",
+        LpObjective::Maximize => "Maximize",
+        LpObjective::Minimize => "Minimize",
     };
-    write!(f, "{}obj: ", obj_type)?;
-    prob.objective().to_lp_file_format(f)?;
+    let write_offset = |f: &mut std::fmt::Formatter| {
+        let offset = prob.objective_offset();
+        if offset != 0. {
+            let sign = if offset < 0. { '-' } else { '+' };
+            write!(f, " {} {}", sign, LpNumber(offset.abs()))?;
+        }
+        Ok(())
+    };
+    if !has_multiple_objectives(prob, features) {
+        write!(f, "{}\n  obj: ", obj_type)?;
+        if let Some((mut objective, constant)) = objective_without_constant(prob, features) {
+            objective.add_term(OBJECTIVE_CONSTANT_VARIABLE, constant);
+            return objective.to_lp_file_format(f);
+        }
+        prob.objective().objective_to_lp_file_format(f)?;
+        return write_offset(f);
+    }
+    let names = objective_names(prob);
+    write!(f, "{} multi-objectives", obj_type)?;
+    for (name, objective) in names.iter().zip(prob.objectives()) {
+        write!(
+            f,
+            "\n  {}: Priority={} Weight={} AbsTol={} RelTol={}\n    ",
+            name,
+            objective.priority,
+            LpNumber(objective.weight),
+            LpNumber(objective.abs_tol),
+            LpNumber(objective.rel_tol)
+        )?;
+        objective.expression.objective_to_lp_file_format(f)?;
+        // the offset is added to each objective
+        write_offset(f)?;
+    }
     Ok(())
 }
 
+/// Whether the objectives are written in a multi-objectives section, instead of the objective
+fn has_multiple_objectives<'a, P: LpProblem<'a>>(prob: &'a P, features: &[LpFeature]) -> bool {
+    features.contains(&LpFeature::MultiObjective) && prob.objectives().next().is_some()
+}
+
+/// The variable that is fixed to 1 to hold the objective constant,
+/// for solvers without [LpFeature::ObjectiveConstant]
+pub(crate) const OBJECTIVE_CONSTANT_VARIABLE: &str = "__obj_constant";
+
+/// The linear part of the objective, and its constant,
+/// when the constant has to be written as the coefficient of [OBJECTIVE_CONSTANT_VARIABLE]
+fn objective_without_constant<'a, P: LpProblem<'a>>(
+    prob: &'a P,
+    features: &[LpFeature],
+) -> Option<(LinearExpression, f64)> {
+    if features.contains(&LpFeature::ObjectiveConstant) || has_multiple_objectives(prob, features) {
+        return None;
+    }
+    // quadratic objectives cannot be parsed, but the solvers that support them take constants
+    let mut objective = parse_expression(&lp_string(&prob.objective())).ok()?;
+    let constant = objective.constant() + prob.objective_offset();
+    objective.set_constant(0.);
+    Some((objective, constant)).filter(|&(_, c)| c != 0.)
+}
+
 fn write_constraints_lp_file_block<'a>(
     prob: &'a impl LpProblem<'a>,
+    features: &[LpFeature],
+    big_m: &[Vec<Constraint<LinearExpression>>],
     f: &mut std::fmt::Formatter,
 ) -> std::fmt::Result {
     write!(f, "\n\nSubject To\n")?;
-    for (idx, constraint) in prob.constraints().enumerate() {
-        write!(f, "  c{}: ", idx)?;
+    let mut names = row_names(prob, features).into_iter();
+    for constraint in prob.constraints() {
+        write!(f, "  {}: ", names.next().unwrap_or_default())?;
+        constraint.to_lp_file_format(f)?;
+        writeln!(f)?;
+    }
+    for range in prob.ranges() {
+        let mut row = |operator: &str, rhs: f64| {
+            write!(f, "  {}: ", names.next().unwrap_or_default())?;
+            range.lhs.to_lp_file_format(f)?;
+            writeln!(f, " {} {}", operator, LpNumber(rhs))
+        };
+        match range.single_side() {
+            Some((Ordering::Less, rhs)) => row("<=", rhs)?,
+            Some((Ordering::Equal, rhs)) => row("=", rhs)?,
+            Some((Ordering::Greater, rhs)) => row(">=", rhs)?,
+            None if features.contains(&LpFeature::Ranges) => {
+                write!(
+                    f,
+                    "  {}: {} <= ",
+                    names.next().unwrap_or_default(),
+                    LpNumber(range.lower)
+                )?;
+                range.lhs.to_lp_file_format(f)?;
+                writeln!(f, " <= {}", LpNumber(range.upper))?;
+            }
+            None => {
+                row(">=", range.lower)?;
+                row("<=", range.upper)?;
+            }
+        }
+    }
+    if features.contains(&LpFeature::Indicators) {
+        for indicator in prob.indicator_constraints() {
+            write!(f, "  {}: ", names.next().unwrap_or_default())?;
+            indicator.to_lp_file_format(f)?;
+            writeln!(f)?;
+        }
+    } else {
+        for constraint in big_m.iter().flatten() {
+            write!(f, "  {}: ", names.next().unwrap_or_default())?;
+            constraint.to_lp_file_format(f)?;
+            writeln!(f)?;
+        }
+    }
+    let lazy: Vec<_> = prob.lazy_constraints().zip(&mut names).collect();
+    let cuts: Vec<_> = prob.user_cuts().zip(&mut names).collect();
+    let mut sections = vec![];
+    for (section, feature, constraints) in [
+        ("Lazy Constraints", LpFeature::LazyConstraints, lazy),
+        ("User Cuts", LpFeature::UserCuts, cuts),
+    ] {
+        if features.contains(&feature) {
+            sections.push((section, constraints));
+        } else {
+            // ordinary constraints must come before the other sections
+            write_named_constraints(&constraints, f)?;
+        }
+    }
+    for (section, constraints) in sections.iter().filter(|(_, c)| !c.is_empty()) {
+        write!(f, "\n{}\n", section)?;
+        write_named_constraints(constraints, f)?;
+    }
+    Ok(())
+}
+
+fn write_named_constraints<E: WriteToLpFileFormat>(
+    constraints: &[(Constraint<E>, String)],
+    f: &mut Formatter,
+) -> fmt::Result {
+    for (constraint, name) in constraints {
+        write!(f, "  {}: ", name)?;
         constraint.to_lp_file_format(f)?;
         writeln!(f)?;
     }
     Ok(())
 }
 
-fn write_bounds_lp_file_block<'a>(prob: &'a impl LpProblem<'a>, f: &mut Formatter) -> fmt::Result {
+fn write_bounds_lp_file_block<'a>(
+    prob: &'a impl LpProblem<'a>,
+    features: &[LpFeature],
+    f: &mut Formatter,
+) -> fmt::Result {
     let mut integers = vec![];
+    let mut binaries = vec![];
+    let mut semis = vec![];
     write!(f, "\nBounds\n")?;
     for variable in prob.variables() {
         let low: f64 = variable.lower_bound();
         let up: f64 = variable.upper_bound();
+        let name = variable.name().to_string();
+        let kind = variable.kind();
+        match kind {
+            VariableKind::Binary => binaries.push(name.clone()),
+            VariableKind::Integer | VariableKind::SemiInteger => integers.push(name.clone()),
+            _ => {}
+        }
+        if kind.is_semi() {
+            semis.push(name.clone());
+        }
+        if kind == VariableKind::Binary && low == 0. && up == 1. {
+            // implied by the Binaries section
+            continue;
+        }
         write!(f, "  ")?;
         if low > f64::NEG_INFINITY {
-            write!(f, "{} <= ", low)?;
+            write!(f, "{} <= ", LpNumber(low))?;
+        } else if up < f64::INFINITY {
+            // a lone upper bound keeps the default lower bound of 0
+            write!(f, "-inf <= ")?;
         }
-        let name = variable.name().to_string();
         write!(f, "{}", name)?;
         if up < f64::INFINITY {
-            write!(f, " <= {}", up)?;
+            write!(f, " <= {}", LpNumber(up))?;
         }
         if low.is_infinite() && up.is_infinite() {
             write!(f, " free")?;
         }
         writeln!(f)?;
-        if variable.is_integer() {
-            integers.push(name);
+    }
+    if objective_without_constant(prob, features).is_some() {
+        writeln!(f, "  {} = 1", OBJECTIVE_CONSTANT_VARIABLE)?;
+    }
+    for (section, names) in [
+        ("Generals", integers),
+        ("Binaries", binaries),
+        ("Semi-continuous", semis),
+    ] {
+        if !names.is_empty() {
+            writeln!(f, "\n{}", section)?;
+            for name in names.iter() {
+                writeln!(f, "  {}", name)?;
+            }
         }
     }
-    if !integers.is_empty() {
-        writeln!(f, "\nGenerals")?;
-        for name in integers.iter() {
-            writeln!(f, "  {}", name)?;
+    Ok(())
+}
+
+fn write_general_constraints_lp_file_block<'a>(
+    prob: &'a impl LpProblem<'a>,
+    f: &mut Formatter,
+) -> fmt::Result {
+    let names = general_constraint_names(prob);
+    if names.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "\nGeneral Constraints")?;
+    for (name, constraint) in names.iter().zip(prob.general_constraints()) {
+        write!(f, "  {}: ", name)?;
+        constraint.to_lp_file_format(f)?;
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+fn write_sos_lp_file_block<'a>(prob: &'a impl LpProblem<'a>, f: &mut Formatter) -> fmt::Result {
+    let names = sos_names(prob);
+    if names.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "\nSOS")?;
+    for (name, sos) in names.iter().zip(prob.sos_constraints()) {
+        let kind = match sos.kind {
+            SosType::S1 => "S1",
+            SosType::S2 => "S2",
+        };
+        write!(f, "  {}: {}::", name, kind)?;
+        for (variable, weight) in &sos.variables {
+            write!(f, " {}:{}", variable, LpNumber(*weight))?;
         }
+        writeln!(f)?;
     }
     Ok(())
 }
@@ -0,0 +1,1013 @@
+//! Read problems written in the .lp file format.
+//!
+//! This is the counterpart of [crate::lp_format]: it parses the objective, `Subject To`,
+//! `Bounds`, `Generals`, `Binaries`, `General Constraints`, `SOS` and `End` sections
+//! of a .lp file into a [Problem].
+//! Section keywords start their line: indented lines are the content of the current section,
+//! so variables can have the name of a keyword, like `bin` or `end`.
+//! Quadratic expressions are not supported.
+//!
+//! ```
+//! use lp_solvers::lp_format::LpProblem;
+//! use lp_solvers::lp_reader::parse_lp;
+//!
+//! let problem = parse_lp("
+//! \\ a small problem
+//! Maximize
+//!   obj: x + 2 y
+//! Subject To
+//!   limit: x + y
+//!          <= 10
+//! Bounds
+//!   y <= 4
+//! Generals
+//!   x
+//! End
+//! ").unwrap();
+//! assert_eq!(problem.variables.len(), 2);
+//! assert_eq!(problem.constraints[0].rhs, 10.);
+//! ```
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Formatter;
+use std::path::Path;
+
+use crate::lp_format::{
+    Constraint, GeneralConstraint, GeneralFunction, IndicatorConstraint, LpObjective,
+    RangeConstraint, SosConstraint, SosType, VariableKind,
+};
+use crate::problem::{LinearExpression, Problem, Variable};
+
+/// An error encountered while reading a problem file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// 1-based line number of the error
+    pub line: usize,
+    /// 1-based column (in characters) of the error
+    pub column: usize,
+    /// description of what went wrong
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Read a .lp file from disk. See [parse_lp].
+pub fn read_lp_file(path: &Path) -> Result<Problem, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot open problem file {:?}: {}", path, e))?;
+    parse_lp(&content).map_err(|e| format!("Invalid problem file {:?}: {}", path, e))
+}
+
+/// Parse a problem in the .lp file format.
+///
+/// Expressions are normalized: duplicate terms are merged and constants on the left hand side
+/// of constraints are moved to the right hand side.
+/// Variables that appear in the file but not in the `Bounds` section get the default
+/// bounds of the format, `0 <= x < +inf`.
+pub fn parse_lp(content: &str) -> Result<Problem, ParseError> {
+    let sections = split_sections(content)?;
+    let mut reader = ProblemReader::default();
+    let mut objective = None;
+    let mut sos = vec![];
+    let mut general_constraints = vec![];
+    let mut lazy_constraints = vec![];
+    let mut user_cuts = vec![];
+    for section in sections {
+        match section.kind {
+            Section::Objective(sense) => {
+                if objective.is_some() {
+                    return Err(section.error("the objective is defined twice"));
+                }
+                let mut cursor = Cursor::new(&section);
+                cursor.skip_label();
+                let expr = reader.expression(&mut cursor)?;
+                if let Some(t) = cursor.peek() {
+                    return Err(t.error(format!("unexpected {} in the objective", t.token)));
+                }
+                objective = Some((sense, expr));
+            }
+            Section::Constraints => reader.constraints(&section)?,
+            Section::LazyConstraints | Section::UserCuts => {
+                let start = reader.constraints.len();
+                reader.constraints(&section)?;
+                let constraints = reader.constraints.drain(start..);
+                if section.kind == Section::LazyConstraints {
+                    lazy_constraints.extend(constraints);
+                } else {
+                    user_cuts.extend(constraints);
+                }
+            }
+            Section::Bounds => reader.bounds(&section)?,
+            Section::Generals => {
+                for name in names(&section)? {
+                    let var = reader.variable(name);
+                    var.kind = match var.kind {
+                        VariableKind::SemiContinuous => VariableKind::SemiInteger,
+                        VariableKind::Continuous => VariableKind::Integer,
+                        kind => kind,
+                    };
+                }
+            }
+            Section::Binaries => {
+                for name in names(&section)? {
+                    let var = reader.variable(name);
+                    var.kind = VariableKind::Binary;
+                    var.lower_bound = 0.;
+                    var.upper_bound = 1.;
+                }
+            }
+            Section::SemiContinuous => {
+                for name in names(&section)? {
+                    let var = reader.variable(name);
+                    var.kind = if var.kind.is_integer() {
+                        VariableKind::SemiInteger
+                    } else {
+                        VariableKind::SemiContinuous
+                    };
+                }
+            }
+            Section::Sos => sos.extend(sos_sets(&section)?),
+            Section::GeneralConstraints => {
+                general_constraints.extend(reader.general_constraints(&section)?)
+            }
+        }
+    }
+    let (sense, objective) = objective
+        .ok_or_else(|| ParseError::new(1, 1, "missing objective section (Minimize or Maximize)"))?;
+    Ok(Problem {
+        name: problem_name(content),
+        sense,
+        objective,
+        variables: reader.variables,
+        constraints: reader.constraints,
+        ranges: reader.ranges,
+        sos,
+        indicators: reader.indicators,
+        general_constraints,
+        lazy_constraints,
+        user_cuts,
+        objective_offset: 0.,
+        objectives: vec![],
+    })
+}
+
+/// The writer puts the problem name in a comment on the first line
+fn problem_name(content: &str) -> String {
+    content
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .and_then(|l| l.strip_prefix('\\'))
+        .map(str::trim)
+        .filter(|name| !name.is_empty() && !name.starts_with('*'))
+        .unwrap_or("lp_solvers_problem")
+        .to_string()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Section {
+    Objective(LpObjective),
+    Constraints,
+    LazyConstraints,
+    UserCuts,
+    Bounds,
+    Generals,
+    Binaries,
+    SemiContinuous,
+    GeneralConstraints,
+    Sos,
+}
+
+enum Keyword {
+    Section(Section),
+    End,
+}
+
+/// Section keywords are alone on their line, and case insensitive
+fn keyword(line: &str) -> Option<Keyword> {
+    let normalized = line
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_lowercase();
+    let section = match normalized.as_str() {
+        "minimize" | "minimise" | "minimum" | "min" => Section::Objective(LpObjective::Minimize),
+        "maximize" | "maximise" | "maximum" | "max" => Section::Objective(LpObjective::Maximize),
+        "subject to" | "such that" | "st" | "s.t." | "st." => Section::Constraints,
+        "lazy constraints" => Section::LazyConstraints,
+        "user cuts" => Section::UserCuts,
+        "bounds" | "bound" => Section::Bounds,
+        "generals" | "general" | "gen" => Section::Generals,
+        "binaries" | "binary" | "bin" => Section::Binaries,
+        "semi-continuous" | "semi" | "semis" => Section::SemiContinuous,
+        "general constraints" => Section::GeneralConstraints,
+        "sos" => Section::Sos,
+        "end" => return Some(Keyword::End),
+        _ => return None,
+    };
+    Some(Keyword::Section(section))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Name(String),
+    Number(f64),
+    Plus,
+    Minus,
+    Star,
+    Colon,
+    Implies,
+    Cmp(Ordering),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Name(n) => write!(f, "'{}'", n),
+            Token::Number(n) => write!(f, "number {}", n),
+            Token::Plus => f.write_str("'+'"),
+            Token::Minus => f.write_str("'-'"),
+            Token::Star => f.write_str("'*'"),
+            Token::Colon => f.write_str("':'"),
+            Token::Implies => f.write_str("'->'"),
+            Token::Cmp(Ordering::Less) => f.write_str("'<='"),
+            Token::Cmp(Ordering::Equal) => f.write_str("'='"),
+            Token::Cmp(Ordering::Greater) => f.write_str("'>='"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Spanned {
+    token: Token,
+    line: usize,
+    column: usize,
+}
+
+impl Spanned {
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line, self.column, message)
+    }
+
+    fn name(&self) -> Option<&str> {
+        match &self.token {
+            Token::Name(n) => Some(n),
+            _ => None,
+        }
+    }
+}
+
+struct SectionTokens {
+    kind: Section,
+    line: usize,
+    tokens: Vec<Spanned>,
+}
+
+impl SectionTokens {
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line, 1, message)
+    }
+
+    fn end_error(&self, expected: &str) -> ParseError {
+        match self.tokens.last() {
+            Some(t) => t.error(format!("unexpected end of section, expected {}", expected)),
+            None => self.error(format!("unexpected end of section, expected {}", expected)),
+        }
+    }
+}
+
+/// Remove comments from the file, keeping line numbers intact.
+/// `\` starts a comment that runs to the end of the line, `\* ... *\` delimits a block comment.
+fn strip_comments(content: &str) -> Result<Vec<String>, ParseError> {
+    let mut lines = Vec::new();
+    let mut block_start = None;
+    for (line_idx, line) in content.lines().enumerate() {
+        let mut kept = String::with_capacity(line.len());
+        let mut chars = line.chars().enumerate().peekable();
+        while let Some((col, c)) = chars.next() {
+            if block_start.is_some() {
+                if c == '*' && chars.peek().map(|&(_, c)| c) == Some('\\') {
+                    chars.next();
+                    block_start = None;
+                }
+                // keep the columns of what follows the comment aligned
+                kept.push(' ');
+            } else if c == '\\' {
+                if chars.peek().map(|&(_, c)| c) == Some('*') {
+                    chars.next();
+                    block_start = Some((line_idx + 1, col + 1));
+                    kept.push_str("  ");
+                } else {
+                    break;
+                }
+            } else {
+                kept.push(c);
+            }
+        }
+        lines.push(kept);
+    }
+    if let Some((line, column)) = block_start {
+        return Err(ParseError::new(line, column, "unterminated block comment"));
+    }
+    Ok(lines)
+}
+
+fn split_sections(content: &str) -> Result<Vec<SectionTokens>, ParseError> {
+    let mut sections: Vec<SectionTokens> = Vec::new();
+    for (idx, line) in strip_comments(content)?.iter().enumerate() {
+        let line_nr = idx + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // inside a section, an indented line is content even if it reads like a keyword
+        let indented = !sections.is_empty() && line.starts_with(char::is_whitespace);
+        match keyword(line).filter(|_| !indented) {
+            Some(Keyword::Section(kind)) => sections.push(SectionTokens {
+                kind,
+                line: line_nr,
+                tokens: vec![],
+            }),
+            Some(Keyword::End) => return Ok(sections),
+            None => {
+                let current = sections.last_mut().ok_or_else(|| {
+                    let column = line.find(|c: char| !c.is_whitespace()).unwrap_or(0) + 1;
+                    ParseError::new(line_nr, column, "expected a section keyword")
+                })?;
+                tokenize_line(line, line_nr, &mut current.tokens)?;
+            }
+        }
+    }
+    Ok(sections)
+}
+
+fn is_name_char(c: char) -> bool {
+    !c.is_whitespace() && !"+-*^<>=:[]\\".contains(c)
+}
+
+fn tokenize_line(line: &str, line_nr: usize, tokens: &mut Vec<Spanned>) -> Result<(), ParseError> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        let mut push = |token| {
+            tokens.push(Spanned {
+                token,
+                line: line_nr,
+                column,
+            })
+        };
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let next = chars.get(i + 1).copied();
+        match c {
+            '+' => push(Token::Plus),
+            '-' if next == Some('>') => {
+                i += 1;
+                push(Token::Implies);
+            }
+            '-' => push(Token::Minus),
+            '*' => push(Token::Star),
+            ':' => push(Token::Colon),
+            '<' | '>' | '=' => {
+                let ordering = match (c, next) {
+                    ('<', _) | ('=', Some('<')) => Ordering::Less,
+                    ('>', _) | ('=', Some('>')) => Ordering::Greater,
+                    _ => Ordering::Equal,
+                };
+                if matches!(next, Some('=') | Some('<') | Some('>')) {
+                    i += 1;
+                }
+                push(Token::Cmp(ordering));
+            }
+            c if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                // exponent, only if it is followed by digits: "2e" is a coefficient and a variable
+                if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                    let mut j = i + 1;
+                    if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                        j += 1;
+                    }
+                    if j < chars.len() && chars[j].is_ascii_digit() {
+                        i = j;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let text: String = chars[start..i].iter().collect();
+                let value = text.parse().map_err(|_| {
+                    ParseError::new(line_nr, column, format!("invalid number '{}'", text))
+                })?;
+                push(Token::Number(value));
+                continue;
+            }
+            '[' | ']' | '^' => {
+                return Err(ParseError::new(
+                    line_nr,
+                    column,
+                    "quadratic expressions are not supported",
+                ))
+            }
+            c if is_name_char(c) => {
+                let start = i;
+                while i < chars.len() && is_name_char(chars[i]) {
+                    i += 1;
+                }
+                push(Token::Name(chars[start..i].iter().collect()));
+                continue;
+            }
+            c => {
+                return Err(ParseError::new(
+                    line_nr,
+                    column,
+                    format!("unexpected character '{}'", c),
+                ))
+            }
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+struct Cursor<'s> {
+    section: &'s SectionTokens,
+    pos: usize,
+}
+
+impl<'s> Cursor<'s> {
+    fn new(section: &'s SectionTokens) -> Self {
+        Cursor { section, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&'s Spanned> {
+        self.section.tokens.get(self.pos)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&'s Spanned> {
+        self.section.tokens.get(self.pos + n)
+    }
+
+    fn next(&mut self) -> Option<&'s Spanned> {
+        let t = self.peek();
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<&'s Spanned, ParseError> {
+        self.next().ok_or_else(|| self.section.end_error(expected))
+    }
+
+    /// Skip a `name:` label, returning the name
+    fn skip_label(&mut self) -> Option<&'s str> {
+        match (self.peek(), self.peek_nth(1)) {
+            (
+                Some(t),
+                Some(Spanned {
+                    token: Token::Colon,
+                    ..
+                }),
+            ) => {
+                self.pos += 2;
+                t.name()
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse a possibly signed number, or infinity
+    fn value(&mut self) -> Result<f64, ParseError> {
+        let mut sign = 1.;
+        loop {
+            let t = self.expect("a number")?;
+            match &t.token {
+                Token::Plus => {}
+                Token::Minus => sign = -sign,
+                Token::Number(n) => return Ok(sign * n),
+                Token::Name(n) if is_infinity(n) => return Ok(sign * f64::INFINITY),
+                other => return Err(t.error(format!("expected a number, found {}", other))),
+            }
+        }
+    }
+
+    fn starts_value(&self) -> bool {
+        match self.peek().map(|t| &t.token) {
+            Some(Token::Number(_)) | Some(Token::Plus) | Some(Token::Minus) => true,
+            Some(Token::Name(n)) => is_infinity(n),
+            _ => false,
+        }
+    }
+
+    fn cmp(&mut self) -> Option<Ordering> {
+        match self.peek() {
+            Some(Spanned {
+                token: Token::Cmp(o),
+                ..
+            }) => {
+                self.pos += 1;
+                Some(*o)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn is_infinity(name: &str) -> bool {
+    name.eq_ignore_ascii_case("inf") || name.eq_ignore_ascii_case("infinity")
+}
+
+/// Read the terms of a single expression, as written by [crate::lp_format::WriteToLpFileFormat]
+pub(crate) fn parse_expression(text: &str) -> Result<LinearExpression, ParseError> {
+    let mut section = SectionTokens {
+        kind: Section::Objective(LpObjective::Minimize),
+        line: 1,
+        tokens: vec![],
+    };
+    for (idx, line) in text.lines().enumerate() {
+        tokenize_line(line, idx + 1, &mut section.tokens)?;
+    }
+    let mut cursor = Cursor::new(&section);
+    let expr = ProblemReader::default().expression(&mut cursor)?;
+    match cursor.peek() {
+        Some(t) => Err(t.error(format!("unexpected {} in expression", t.token))),
+        None => Ok(expr),
+    }
+}
+
+#[derive(Default)]
+struct ProblemReader {
+    variables: Vec<Variable>,
+    indices: HashMap<String, usize>,
+    constraints: Vec<Constraint<LinearExpression>>,
+    ranges: Vec<RangeConstraint<LinearExpression>>,
+    indicators: Vec<IndicatorConstraint<LinearExpression>>,
+}
+
+impl ProblemReader {
+    fn variable(&mut self, name: &str) -> &mut Variable {
+        let variables = &mut self.variables;
+        let idx = *self.indices.entry(name.to_string()).or_insert_with(|| {
+            variables.push(Variable {
+                name: name.to_string(),
+                kind: VariableKind::Continuous,
+                lower_bound: 0.,
+                upper_bound: f64::INFINITY,
+            });
+            variables.len() - 1
+        });
+        &mut self.variables[idx]
+    }
+
+    /// Parse `[+-] [coef] [*] name` terms until something that can not be part of an expression
+    fn expression(&mut self, cursor: &mut Cursor) -> Result<LinearExpression, ParseError> {
+        let mut expr = LinearExpression::default();
+        let mut first = true;
+        loop {
+            let mut sign = 1.;
+            let mut has_sign = false;
+            while let Some(t) = cursor.peek() {
+                match t.token {
+                    Token::Plus => {}
+                    Token::Minus => sign = -sign,
+                    _ => break,
+                }
+                has_sign = true;
+                cursor.pos += 1;
+            }
+            if !first && !has_sign {
+                return Ok(expr);
+            }
+            let t = match cursor.peek() {
+                Some(t) => t,
+                None if has_sign => return Err(cursor.section.end_error("a term")),
+                None => return Ok(expr),
+            };
+            match &t.token {
+                Token::Number(n) => {
+                    cursor.pos += 1;
+                    if let Some(Spanned {
+                        token: Token::Star, ..
+                    }) = cursor.peek()
+                    {
+                        cursor.pos += 1;
+                    }
+                    match cursor.peek() {
+                        Some(var) if var.name().is_some() && !is_label(cursor, 0) => {
+                            cursor.pos += 1;
+                            self.add_term(&mut expr, var, sign * n)
+                        }
+                        _ => expr.set_constant(expr.constant() + sign * n),
+                    }
+                }
+                Token::Name(_) if !is_label(cursor, 0) => {
+                    cursor.pos += 1;
+                    self.add_term(&mut expr, t, sign)
+                }
+                _ if first && !has_sign => return Ok(expr),
+                other => return Err(t.error(format!("expected a term, found {}", other))),
+            }
+            first = false;
+        }
+    }
+
+    fn add_term(&mut self, expr: &mut LinearExpression, var: &Spanned, coefficient: f64) {
+        let name = var.name().expect("terms are names");
+        self.variable(name);
+        expr.add_term(name, coefficient);
+    }
+
+    fn constraints(&mut self, section: &SectionTokens) -> Result<(), ParseError> {
+        let mut cursor = Cursor::new(section);
+        while let Some(start) = cursor.peek() {
+            let name = cursor.skip_label().map(str::to_string);
+            let indicator = Self::indicator_start(&mut cursor)?;
+            let range_start = match indicator {
+                Some(_) => None,
+                None => Self::range_start(&mut cursor),
+            };
+            let lhs = self.expression(&mut cursor)?;
+            let operator = match cursor.cmp() {
+                Some(o) => o,
+                None => {
+                    return Err(match cursor.peek() {
+                        Some(t) => {
+                            t.error(format!("expected a comparison operator, found {}", t.token))
+                        }
+                        None => section.end_error("a comparison operator"),
+                    })
+                }
+            };
+            let rhs = cursor.value()?;
+            let mut lhs = lhs;
+            if lhs.terms().next().is_none() {
+                let label = name.map(|n| format!(" {}", n)).unwrap_or_default();
+                return Err(start.error(format!("constraint{} has no variables", label)));
+            }
+            let constant = lhs.constant();
+            lhs.set_constant(0.);
+            let constraint = Constraint {
+                lhs,
+                operator,
+                rhs: rhs - constant,
+                name,
+            };
+            if let Some((variable, value)) = indicator {
+                self.variable(&variable);
+                self.indicators.push(constraint.when(&variable, value));
+                continue;
+            }
+            match range_start {
+                None => self.constraints.push(constraint),
+                Some((first, value)) => {
+                    let (lower, upper) = match (first, operator) {
+                        (Ordering::Less, Ordering::Less) => (value, rhs),
+                        (Ordering::Greater, Ordering::Greater) => (rhs, value),
+                        _ => {
+                            return Err(start
+                                .error("both sides of a range must use <= or both must use >="))
+                        }
+                    };
+                    self.ranges.push(RangeConstraint {
+                        lhs: constraint.lhs,
+                        lower: lower - constant,
+                        upper: upper - constant,
+                        name: constraint.name,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The condition of an indicator constraint (`b = 1 ->`), if the constraint is one
+    fn indicator_start(cursor: &mut Cursor) -> Result<Option<(String, bool)>, ParseError> {
+        let implies = cursor
+            .peek_nth(3)
+            .is_some_and(|t| t.token == Token::Implies);
+        let (variable, equal) = match (cursor.peek(), cursor.peek_nth(1)) {
+            (Some(variable), Some(equal)) if implies => (variable, equal),
+            _ => return Ok(None),
+        };
+        let name = variable.name().ok_or_else(|| {
+            variable.error(format!(
+                "expected an indicator variable, found {}",
+                variable.token
+            ))
+        })?;
+        if equal.token != Token::Cmp(Ordering::Equal) {
+            return Err(equal.error(format!("expected '=', found {}", equal.token)));
+        }
+        let value = cursor.peek_nth(2).expect("checked above");
+        let value = match value.token {
+            Token::Number(n) if n == 0. || n == 1. => n == 1.,
+            _ => return Err(value.error("the value of an indicator variable must be 0 or 1")),
+        };
+        cursor.pos += 4;
+        Ok(Some((name.to_string(), value)))
+    }
+
+    /// The first bound of a range constraint (`lower <=` or `upper >=`), if the constraint is one
+    fn range_start(cursor: &mut Cursor) -> Option<(Ordering, f64)> {
+        if !cursor.starts_value() {
+            return None;
+        }
+        let start = cursor.pos;
+        if let Ok(value) = cursor.value() {
+            if let Some(operator) = cursor.cmp() {
+                return Some((operator, value));
+            }
+        }
+        cursor.pos = start;
+        None
+    }
+
+    fn bounds(&mut self, section: &SectionTokens) -> Result<(), ParseError> {
+        let mut cursor = Cursor::new(section);
+        while cursor.peek().is_some() {
+            if cursor.starts_value() {
+                // lo <= x [<= up]
+                let value = cursor.value()?;
+                let op = cursor
+                    .cmp()
+                    .ok_or_else(|| bound_error(&cursor, "a comparison operator"))?;
+                let name = bound_variable(&mut cursor)?;
+                self.set_bound(name, op.reverse(), value);
+                if let Some(op) = cursor.cmp() {
+                    let value = cursor.value()?;
+                    self.set_bound(name, op, value);
+                }
+            } else {
+                // x free | x <= up | x >= lo | x = v
+                let name = bound_variable(&mut cursor)?;
+                match cursor.peek() {
+                    Some(t) if t.name().is_some_and(|n| n.eq_ignore_ascii_case("free")) => {
+                        cursor.pos += 1;
+                        let var = self.variable(name);
+                        var.lower_bound = f64::NEG_INFINITY;
+                        var.upper_bound = f64::INFINITY;
+                    }
+                    _ => {
+                        let op = cursor.cmp().ok_or_else(|| {
+                            bound_error(&cursor, "a comparison operator or 'free'")
+                        })?;
+                        let value = cursor.value()?;
+                        self.set_bound(name, op, value);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Read `name: r = FUNCTION ( x , y , 2 )` constraints, with spaces around the separators
+    /// as [GeneralConstraint] writes them.
+    /// Piecewise-linear functions are followed by their points: `: ( x0 , y0 ) ( x1 , y1 )`
+    fn general_constraints(
+        &mut self,
+        section: &SectionTokens,
+    ) -> Result<Vec<GeneralConstraint>, ParseError> {
+        let mut cursor = Cursor::new(section);
+        let mut constraints = vec![];
+        while cursor.peek().is_some() {
+            let name = cursor.skip_label().map(str::to_string);
+            let resultant = bound_variable(&mut cursor)?;
+            self.variable(resultant);
+            let t = cursor.expect("'='")?;
+            if t.token != Token::Cmp(Ordering::Equal) {
+                return Err(t.error(format!("expected '=', found {}", t.token)));
+            }
+            let function = cursor.expect("a function")?;
+            let function_name = function.name().map(str::to_ascii_uppercase);
+            let (variables, constant) = self.operands(&mut cursor)?;
+            let single = |variables: Vec<String>| match variables.as_slice() {
+                [variable] if constant.is_none() => Ok(variable.clone()),
+                _ => Err(function.error(format!("{} takes a single variable", function.token))),
+            };
+            let without_constant = |variables: Vec<String>| match constant {
+                None => Ok(variables),
+                Some(_) => Err(function.error(format!("{} takes no constant", function.token))),
+            };
+            let function = match function_name.as_deref() {
+                Some("MIN") => GeneralFunction::Min {
+                    variables,
+                    constant,
+                },
+                Some("MAX") => GeneralFunction::Max {
+                    variables,
+                    constant,
+                },
+                Some("ABS") => GeneralFunction::Abs(single(variables)?),
+                Some("AND") => GeneralFunction::And(without_constant(variables)?),
+                Some("OR") => GeneralFunction::Or(without_constant(variables)?),
+                Some("PWL") => {
+                    let variable = single(variables)?;
+                    let t = cursor.expect("':'")?;
+                    if t.token != Token::Colon {
+                        return Err(t.error(format!("expected ':', found {}", t.token)));
+                    }
+                    let mut points = vec![];
+                    while is_symbol(cursor.peek(), "(") {
+                        cursor.pos += 1;
+                        let x = cursor.value()?;
+                        expect_symbol(&mut cursor, ",")?;
+                        let y = cursor.value()?;
+                        expect_symbol(&mut cursor, ")")?;
+                        points.push((x, y));
+                    }
+                    GeneralFunction::Pwl { variable, points }
+                }
+                _ => {
+                    return Err(function.error(format!(
+                        "unsupported general constraint function {}",
+                        function.token
+                    )))
+                }
+            };
+            constraints.push(GeneralConstraint {
+                name,
+                resultant: resultant.to_string(),
+                function,
+            });
+        }
+        Ok(constraints)
+    }
+
+    /// The variables and the optional constant of `( x , y , 2 )`
+    fn operands(&mut self, cursor: &mut Cursor) -> Result<(Vec<String>, Option<f64>), ParseError> {
+        expect_symbol(cursor, "(")?;
+        let mut variables = vec![];
+        let mut constant = None;
+        loop {
+            if cursor.starts_value() {
+                let t = cursor.peek().expect("checked by starts_value");
+                if constant.is_some() {
+                    return Err(t.error("a general constraint takes a single constant"));
+                }
+                constant = Some(cursor.value()?);
+            } else {
+                let name = bound_variable(cursor)?;
+                self.variable(name);
+                variables.push(name.to_string());
+            }
+            let t = cursor.expect("',' or ')'")?;
+            match t.name() {
+                Some(",") => {}
+                Some(")") => return Ok((variables, constant)),
+                _ => return Err(t.error(format!("expected ',' or ')', found {}", t.token))),
+            }
+        }
+    }
+
+    /// Apply `name op value`
+    fn set_bound(&mut self, name: &str, op: Ordering, value: f64) {
+        let var = self.variable(name);
+        if op != Ordering::Less {
+            var.lower_bound = value;
+        }
+        if op != Ordering::Greater {
+            var.upper_bound = value;
+        }
+    }
+}
+
+/// Read the special ordered sets of a section.
+/// Sets are written `name: S1:: x:1 y:2`, the name being optional
+fn sos_sets(section: &SectionTokens) -> Result<Vec<SosConstraint>, ParseError> {
+    let mut cursor = Cursor::new(section);
+    let mut sets = vec![];
+    while cursor.peek().is_some() {
+        let name = if is_sos_header(&cursor, 0) {
+            None
+        } else {
+            cursor.skip_label().map(str::to_string)
+        };
+        let t = cursor.expect("a set type")?;
+        let kind = match t.name().and_then(sos_type) {
+            Some(kind) if is_colon(cursor.peek()) && is_colon(cursor.peek_nth(1)) => kind,
+            _ => return Err(t.error(format!("expected S1:: or S2::, found {}", t.token))),
+        };
+        cursor.pos += 2;
+        let mut variables = vec![];
+        while cursor.peek().is_some() && !is_sos_header(&cursor, 0) && !is_sos_header(&cursor, 2) {
+            let t = cursor.expect("a variable name")?;
+            let variable = match t.name() {
+                Some(name) if is_colon(cursor.peek()) => name,
+                _ => return Err(t.error(format!("expected name:weight, found {}", t.token))),
+            };
+            cursor.pos += 1;
+            variables.push((variable.to_string(), cursor.value()?));
+        }
+        sets.push(SosConstraint {
+            name,
+            kind,
+            variables,
+            priority: None,
+        });
+    }
+    Ok(sets)
+}
+
+/// Whether the n-th token starts `S1::` or `S2::`
+fn is_sos_header(cursor: &Cursor, n: usize) -> bool {
+    cursor
+        .peek_nth(n)
+        .and_then(Spanned::name)
+        .and_then(sos_type)
+        .is_some()
+        && is_colon(cursor.peek_nth(n + 1))
+        && is_colon(cursor.peek_nth(n + 2))
+}
+
+fn sos_type(name: &str) -> Option<SosType> {
+    match name.to_ascii_uppercase().as_str() {
+        "S1" => Some(SosType::S1),
+        "S2" => Some(SosType::S2),
+        _ => None,
+    }
+}
+
+fn is_colon(t: Option<&Spanned>) -> bool {
+    matches!(
+        t,
+        Some(Spanned {
+            token: Token::Colon,
+            ..
+        })
+    )
+}
+
+/// Whether the token at offset n from the cursor is the name of a constraint
+fn is_label(cursor: &Cursor, n: usize) -> bool {
+    matches!(
+        cursor.peek_nth(n + 1),
+        Some(Spanned {
+            token: Token::Colon,
+            ..
+        })
+    )
+}
+
+/// Whether the token is the given separator. `(`, `)` and `,` are allowed in names,
+/// so separators are names on their own.
+fn is_symbol(t: Option<&Spanned>, symbol: &str) -> bool {
+    t.and_then(Spanned::name) == Some(symbol)
+}
+
+fn expect_symbol(cursor: &mut Cursor, symbol: &str) -> Result<(), ParseError> {
+    let t = cursor.expect(&format!("'{}'", symbol))?;
+    if t.name() == Some(symbol) {
+        Ok(())
+    } else {
+        Err(t.error(format!("expected '{}', found {}", symbol, t.token)))
+    }
+}
+
+fn bound_error(cursor: &Cursor, expected: &str) -> ParseError {
+    match cursor.peek() {
+        Some(t) => t.error(format!("expected {}, found {}", expected, t.token)),
+        None => cursor.section.end_error(expected),
+    }
+}
+
+fn bound_variable<'s>(cursor: &mut Cursor<'s>) -> Result<&'s str, ParseError> {
+    let t = cursor.expect("a variable name")?;
+    t.name()
+        .ok_or_else(|| t.error(format!("expected a variable name, found {}", t.token)))
+}
+
+fn names(section: &SectionTokens) -> Result<Vec<&str>, ParseError> {
+    section
+        .tokens
+        .iter()
+        .map(|t| {
+            t.name()
+                .ok_or_else(|| t.error(format!("expected a variable name, found {}", t.token)))
+        })
+        .collect()
+}
@@ -0,0 +1,503 @@
+//! Build problems with typed variable handles and arithmetic operators,
+//! instead of concatenating strings.
+//!
+//! ```
+//! use lp_solvers::model::{Expr, ProblemBuilder};
+//!
+//! let mut builder = ProblemBuilder::new("diet");
+//! let x = builder.continuous("x", 0., 10.);
+//! let y = builder.integer("y", 0., 5.);
+//! builder.add_constraint((2. * x + y).leq(8.));
+//! builder.add_constraint(x.geq(y - 1.));
+//! builder.maximize(vec![x, y].into_iter().sum::<Expr>() * 3.);
+//! let pb = builder.build();
+//! assert_eq!(pb.objective.to_string(), "3 x + 3 y");
+//! assert_eq!(pb.constraints[0].lhs.to_string(), "2 x + y");
+//! assert_eq!(pb.constraints[1].lhs.to_string(), "x - y");
+//! assert_eq!(pb.constraints[1].rhs, -1.);
+//! ```
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::lp_format::{
+    Constraint, GeneralConstraint, GeneralFunction, IndicatorConstraint, LpObjective, Objective,
+    RangeConstraint, SosConstraint, SosType, VariableKind,
+};
+use crate::problem::{LinearExpression, Problem, Variable};
+
+/// A handle to a variable of a [ProblemBuilder].
+///
+/// Handles are only meaningful for the builder that created them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Var(usize);
+
+/// A linear combination of variables, plus a constant
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Expr {
+    terms: Vec<(Var, f64)>,
+    constant: f64,
+}
+
+impl Expr {
+    /// The (variable, coefficient) pairs of the expression, in insertion order.
+    /// The same variable may appear several times.
+    pub fn terms(&self) -> &[(Var, f64)] {
+        &self.terms
+    }
+
+    /// The constant part of the expression
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+
+    /// The constraint `self <= rhs`
+    pub fn leq(self, rhs: impl Into<Expr>) -> Constraint<Expr> {
+        self.compare(Ordering::Less, rhs.into())
+    }
+
+    /// The constraint `self >= rhs`
+    pub fn geq(self, rhs: impl Into<Expr>) -> Constraint<Expr> {
+        self.compare(Ordering::Greater, rhs.into())
+    }
+
+    /// The constraint `self = rhs`
+    pub fn eq(self, rhs: impl Into<Expr>) -> Constraint<Expr> {
+        self.compare(Ordering::Equal, rhs.into())
+    }
+
+    /// The constraint `lower <= self <= upper`
+    pub fn between(self, lower: f64, upper: f64) -> RangeConstraint<Expr> {
+        RangeConstraint {
+            lhs: self,
+            lower,
+            upper,
+            name: None,
+        }
+    }
+
+    /// Variables end up on the left hand side, and the constant on the right hand side
+    fn compare(self, operator: Ordering, rhs: Expr) -> Constraint<Expr> {
+        let mut lhs = self - rhs;
+        let rhs = -lhs.constant;
+        lhs.constant = 0.;
+        Constraint {
+            lhs,
+            operator,
+            rhs,
+            name: None,
+        }
+    }
+}
+
+impl Var {
+    /// The constraint `self <= rhs`
+    pub fn leq(self, rhs: impl Into<Expr>) -> Constraint<Expr> {
+        Expr::from(self).leq(rhs)
+    }
+
+    /// The constraint `self >= rhs`
+    pub fn geq(self, rhs: impl Into<Expr>) -> Constraint<Expr> {
+        Expr::from(self).geq(rhs)
+    }
+
+    /// The constraint `self = rhs`
+    pub fn eq(self, rhs: impl Into<Expr>) -> Constraint<Expr> {
+        Expr::from(self).eq(rhs)
+    }
+
+    /// The constraint `lower <= self <= upper`
+    pub fn between(self, lower: f64, upper: f64) -> RangeConstraint<Expr> {
+        Expr::from(self).between(lower, upper)
+    }
+}
+
+impl From<Var> for Expr {
+    fn from(var: Var) -> Self {
+        Expr {
+            terms: vec![(var, 1.)],
+            constant: 0.,
+        }
+    }
+}
+
+impl From<f64> for Expr {
+    fn from(constant: f64) -> Self {
+        Expr {
+            terms: vec![],
+            constant,
+        }
+    }
+}
+
+impl<T: Into<Expr>> AddAssign<T> for Expr {
+    fn add_assign(&mut self, rhs: T) {
+        let rhs = rhs.into();
+        self.terms.extend(rhs.terms);
+        self.constant += rhs.constant;
+    }
+}
+
+impl<T: Into<Expr>> SubAssign<T> for Expr {
+    fn sub_assign(&mut self, rhs: T) {
+        *self += -rhs.into();
+    }
+}
+
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        self * -1.
+    }
+}
+
+impl Neg for Var {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        self * -1.
+    }
+}
+
+impl Mul<f64> for Expr {
+    type Output = Expr;
+
+    fn mul(mut self, factor: f64) -> Expr {
+        for (_, coefficient) in &mut self.terms {
+            *coefficient *= factor;
+        }
+        self.constant *= factor;
+        self
+    }
+}
+
+impl Mul<f64> for Var {
+    type Output = Expr;
+
+    fn mul(self, factor: f64) -> Expr {
+        Expr::from(self) * factor
+    }
+}
+
+impl Mul<Expr> for f64 {
+    type Output = Expr;
+
+    fn mul(self, expr: Expr) -> Expr {
+        expr * self
+    }
+}
+
+impl Mul<Var> for f64 {
+    type Output = Expr;
+
+    fn mul(self, var: Var) -> Expr {
+        var * self
+    }
+}
+
+impl<T: Into<Expr>> Add<T> for Expr {
+    type Output = Expr;
+
+    fn add(mut self, rhs: T) -> Expr {
+        self += rhs;
+        self
+    }
+}
+
+impl<T: Into<Expr>> Add<T> for Var {
+    type Output = Expr;
+
+    fn add(self, rhs: T) -> Expr {
+        Expr::from(self) + rhs
+    }
+}
+
+impl<T: Into<Expr>> Sub<T> for Expr {
+    type Output = Expr;
+
+    fn sub(mut self, rhs: T) -> Expr {
+        self -= rhs;
+        self
+    }
+}
+
+impl<T: Into<Expr>> Sub<T> for Var {
+    type Output = Expr;
+
+    fn sub(self, rhs: T) -> Expr {
+        Expr::from(self) - rhs
+    }
+}
+
+impl<T: Into<Expr>> Sum<T> for Expr {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Expr::default(), |acc, e| acc + e)
+    }
+}
+
+/// Creates variables and collects constraints, then builds a [Problem]
+#[derive(Debug, Clone)]
+pub struct ProblemBuilder {
+    name: String,
+    sense: LpObjective,
+    objective: Expr,
+    variables: Vec<Variable>,
+    names: HashSet<String>,
+    constraints: Vec<Constraint<Expr>>,
+    ranges: Vec<RangeConstraint<Expr>>,
+    sos: Vec<SosConstraint>,
+    indicators: Vec<IndicatorConstraint<Expr>>,
+    general_constraints: Vec<GeneralConstraint>,
+    lazy_constraints: Vec<Constraint<Expr>>,
+    user_cuts: Vec<Constraint<Expr>>,
+    objectives: Vec<Objective<Expr>>,
+}
+
+impl ProblemBuilder {
+    /// A problem with no variable, that minimizes 0
+    pub fn new(name: &str) -> Self {
+        ProblemBuilder {
+            name: name.to_string(),
+            sense: LpObjective::Minimize,
+            objective: Expr::default(),
+            variables: vec![],
+            names: HashSet::new(),
+            constraints: vec![],
+            ranges: vec![],
+            sos: vec![],
+            indicators: vec![],
+            general_constraints: vec![],
+            lazy_constraints: vec![],
+            user_cuts: vec![],
+            objectives: vec![],
+        }
+    }
+
+    /// Add a variable to the problem.
+    /// If its name is already used, a number is appended to it to make it unique.
+    pub fn add_variable(&mut self, mut variable: Variable) -> Var {
+        if self.names.contains(&variable.name) {
+            variable.name = (2..)
+                .map(|n| format!("{}{}", variable.name, n))
+                .find(|name| !self.names.contains(name))
+                .expect("unbounded range");
+        }
+        self.names.insert(variable.name.clone());
+        self.variables.push(variable);
+        Var(self.variables.len() - 1)
+    }
+
+    /// Add a continuous variable with the given bounds
+    pub fn continuous(&mut self, name: &str, lower_bound: f64, upper_bound: f64) -> Var {
+        self.add_variable(Variable {
+            name: name.to_string(),
+            kind: VariableKind::Continuous,
+            lower_bound,
+            upper_bound,
+        })
+    }
+
+    /// Add an integer variable with the given bounds
+    pub fn integer(&mut self, name: &str, lower_bound: f64, upper_bound: f64) -> Var {
+        self.add_variable(Variable {
+            name: name.to_string(),
+            kind: VariableKind::Integer,
+            lower_bound,
+            upper_bound,
+        })
+    }
+
+    /// Add a variable that is either 0 or 1
+    pub fn binary(&mut self, name: &str) -> Var {
+        self.add_variable(Variable {
+            name: name.to_string(),
+            kind: VariableKind::Binary,
+            lower_bound: 0.,
+            upper_bound: 1.,
+        })
+    }
+
+    /// Add a variable that is either 0, or between the given bounds
+    pub fn semi_continuous(&mut self, name: &str, lower_bound: f64, upper_bound: f64) -> Var {
+        self.add_variable(Variable {
+            name: name.to_string(),
+            kind: VariableKind::SemiContinuous,
+            lower_bound,
+            upper_bound,
+        })
+    }
+
+    /// The name of the variable in the built problem, and in solutions
+    pub fn name(&self, var: Var) -> &str {
+        &self.variables[var.0].name
+    }
+
+    /// Set the objective to minimize
+    pub fn minimize(&mut self, objective: impl Into<Expr>) {
+        self.sense = LpObjective::Minimize;
+        self.objective = objective.into();
+    }
+
+    /// Set the objective to maximize
+    pub fn maximize(&mut self, objective: impl Into<Expr>) {
+        self.sense = LpObjective::Maximize;
+        self.objective = objective.into();
+    }
+
+    /// Add one of the objectives of a problem with several objectives.
+    /// They replace the objective given to [ProblemBuilder::minimize] or [ProblemBuilder::maximize],
+    /// which still set the direction of the optimization.
+    pub fn add_objective(&mut self, objective: Objective<Expr>) {
+        self.objectives.push(objective);
+    }
+
+    /// Add a constraint, usually built with [Expr::leq], [Expr::geq] or [Expr::eq]
+    pub fn add_constraint(&mut self, constraint: Constraint<Expr>) {
+        self.constraints.push(constraint);
+    }
+
+    /// Add a constraint that the solver only checks when it finds a solution
+    pub fn add_lazy_constraint(&mut self, constraint: Constraint<Expr>) {
+        self.lazy_constraints.push(constraint);
+    }
+
+    /// Add a constraint that is implied by the others, but helps cut off fractional solutions
+    pub fn add_user_cut(&mut self, constraint: Constraint<Expr>) {
+        self.user_cuts.push(constraint);
+    }
+
+    /// Add a two-sided constraint, usually built with [Expr::between]
+    pub fn add_range(&mut self, range: RangeConstraint<Expr>) {
+        self.ranges.push(range);
+    }
+
+    /// Add a constraint that only applies when the binary variable `indicator` is equal to `value`
+    pub fn add_indicator(&mut self, indicator: Var, value: bool, constraint: Constraint<Expr>) {
+        let indicator = constraint.when(self.name(indicator), value);
+        self.indicators.push(indicator);
+    }
+
+    /// Add a constraint that sets `resultant` to a function of other variables.
+    /// Returns the constraint, to set its name. Only supported by Gurobi.
+    pub fn add_general_constraint(
+        &mut self,
+        resultant: Var,
+        function: GeneralFunction,
+    ) -> &mut GeneralConstraint {
+        let constraint = GeneralConstraint::new(self.name(resultant), function);
+        self.general_constraints.push(constraint);
+        self.general_constraints.last_mut().expect("just pushed")
+    }
+
+    /// Add a special ordered set of variables, with their weights.
+    /// Returns the set, to set its name or priority.
+    pub fn add_sos(
+        &mut self,
+        kind: SosType,
+        variables: impl IntoIterator<Item = (Var, f64)>,
+    ) -> &mut SosConstraint {
+        let variables = variables
+            .into_iter()
+            .map(|(var, weight)| (self.name(var).to_string(), weight))
+            .collect();
+        self.sos.push(SosConstraint {
+            name: None,
+            kind,
+            variables,
+            priority: None,
+        });
+        self.sos.last_mut().expect("just pushed")
+    }
+
+    /// Build the problem. Panics if an expression uses a variable from another builder.
+    pub fn build(&self) -> Problem {
+        // the constant of the objective is kept apart, so that every solver accepts it
+        let mut objective = self.linear(&self.objective);
+        let objective_offset = objective.constant();
+        objective.set_constant(0.);
+        Problem {
+            name: self.name.clone(),
+            sense: self.sense,
+            objective,
+            variables: self.variables.clone(),
+            constraints: self
+                .constraints
+                .iter()
+                .map(|c| self.linear_constraint(c))
+                .collect(),
+            ranges: self
+                .ranges
+                .iter()
+                .map(|r| {
+                    let mut lhs = self.linear(&r.lhs);
+                    let constant = lhs.constant();
+                    lhs.set_constant(0.);
+                    RangeConstraint {
+                        lhs,
+                        lower: r.lower - constant,
+                        upper: r.upper - constant,
+                        name: r.name.clone(),
+                    }
+                })
+                .collect(),
+            sos: self.sos.clone(),
+            indicators: self
+                .indicators
+                .iter()
+                .map(|i| IndicatorConstraint {
+                    indicator: i.indicator.clone(),
+                    value: i.value,
+                    constraint: self.linear_constraint(&i.constraint),
+                })
+                .collect(),
+            general_constraints: self.general_constraints.clone(),
+            lazy_constraints: self
+                .lazy_constraints
+                .iter()
+                .map(|c| self.linear_constraint(c))
+                .collect(),
+            user_cuts: self
+                .user_cuts
+                .iter()
+                .map(|c| self.linear_constraint(c))
+                .collect(),
+            objective_offset,
+            objectives: self
+                .objectives
+                .iter()
+                .map(|o| Objective {
+                    name: o.name.clone(),
+                    expression: self.linear(&o.expression),
+                    priority: o.priority,
+                    weight: o.weight,
+                    abs_tol: o.abs_tol,
+                    rel_tol: o.rel_tol,
+                })
+                .collect(),
+        }
+    }
+
+    fn linear_constraint(&self, c: &Constraint<Expr>) -> Constraint<LinearExpression> {
+        let mut lhs = self.linear(&c.lhs);
+        // a constraint built by hand may still have a constant
+        let rhs = c.rhs - lhs.constant();
+        lhs.set_constant(0.);
+        Constraint {
+            lhs,
+            operator: c.operator,
+            rhs,
+            name: c.name.clone(),
+        }
+    }
+
+    fn linear(&self, expr: &Expr) -> LinearExpression {
+        LinearExpression::new(
+            expr.terms
+                .iter()
+                .map(|&(var, coefficient)| (self.name(var), coefficient)),
+            expr.constant,
+        )
+    }
+}
@@ -0,0 +1,439 @@
+//! Write problems in the MPS format.
+//!
+//! Every [LpProblem] can be exported to MPS, in its fixed or free variant.
+//! Expressions are read from their .lp representation, so they must be linear.
+//!
+//! ```
+//! use lp_solvers::lp_format::{LpObjective, VariableKind};
+//! use lp_solvers::mps_format::{write_mps, MpsFormat};
+//! use lp_solvers::problem::{Problem, StrExpression, Variable};
+//!
+//! let pb = Problem::new(
+//!     "example",
+//!     LpObjective::Minimize,
+//!     StrExpression("x".to_string()),
+//!     vec![Variable::new("x", VariableKind::Continuous, 1., 5.)],
+//!     vec![],
+//! );
+//! let mut mps = Vec::new();
+//! write_mps(&pb, MpsFormat::Free, &mut mps).unwrap();
+//! assert!(String::from_utf8(mps).unwrap().starts_with("NAME example\n"));
+//! ```
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io;
+use std::io::Write;
+
+use crate::lp_format::{
+    big_m_constraints, lp_string, row_names, sos_names, AsVariable, Constraint, LpFeature,
+    LpObjective, LpProblem, SosType, VariableKind, WriteToLpFileFormat,
+};
+use crate::lp_reader::parse_expression;
+use crate::problem::LinearExpression;
+
+/// The two flavors of the MPS format
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum MpsFormat {
+    /// Fields start at fixed columns. Names are limited to 8 characters.
+    Fixed,
+    /// Fields are separated by whitespace
+    Free,
+}
+
+const OBJECTIVE_ROW: &str = "obj";
+
+/// Write the problem in the given MPS format.
+///
+/// Fails with [io::ErrorKind::InvalidData] if the problem cannot be represented in this format,
+/// for instance because an expression is not linear, or a name is too long for the fixed format.
+pub fn write_mps<'a, P: LpProblem<'a>, W: Write>(
+    problem: &'a P,
+    format: MpsFormat,
+    out: W,
+) -> io::Result<()> {
+    if problem.general_constraints().next().is_some() {
+        return Err(invalid(
+            "general constraints cannot be written in the MPS format".to_string(),
+        ));
+    }
+    let model = MpsModel::new(problem)?;
+    let mut w = MpsWriter { out, format };
+    model.write(problem, &mut w)
+}
+
+/// The names of the rows of the MPS file of the problem, objective excluded, in order.
+/// Two-sided ranges are a single row, and indicator constraints are written as big-M rows.
+pub(crate) fn mps_row_names<'a, P: LpProblem<'a>>(problem: &'a P) -> Vec<String> {
+    let features: Vec<_> = LpFeature::ALL
+        .iter()
+        .copied()
+        .filter(|&f| f != LpFeature::Indicators)
+        .collect();
+    row_names(problem, &features)
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn terms_of<E: WriteToLpFileFormat>(expr: &E, what: &str) -> io::Result<LinearExpression> {
+    let text = lp_string(expr);
+    parse_expression(&text).map_err(|e| {
+        invalid(format!(
+            "cannot write {} {:?} in the MPS format: {}",
+            what, text, e
+        ))
+    })
+}
+
+fn constraint_row<E: WriteToLpFileFormat>(
+    constraint: &Constraint<E>,
+    name: String,
+) -> io::Result<(Row, LinearExpression)> {
+    let lhs = terms_of(&constraint.lhs, &format!("constraint {}", name))?;
+    let row = Row {
+        name,
+        kind: row_kind(constraint.operator),
+        rhs: constraint.rhs - lhs.constant(),
+        range: None,
+    };
+    Ok((row, lhs))
+}
+
+struct Column {
+    name: String,
+    kind: VariableKind,
+    lower_bound: f64,
+    upper_bound: f64,
+    /// (row index, coefficient). The objective is row 0
+    entries: Vec<(usize, f64)>,
+}
+
+struct Row {
+    name: String,
+    kind: &'static str,
+    rhs: f64,
+    /// the row is `rhs <= expr <= rhs + range`
+    range: Option<f64>,
+}
+
+/// The problem, stored column by column
+struct MpsModel {
+    rows: Vec<Row>,
+    columns: Vec<Column>,
+}
+
+impl MpsModel {
+    fn new<'a, P: LpProblem<'a>>(problem: &'a P) -> io::Result<Self> {
+        let mut columns = Vec::new();
+        let mut indices = HashMap::new();
+        for var in problem.variables() {
+            indices.insert(var.name().to_string(), columns.len());
+            columns.push(Column {
+                name: var.name().to_string(),
+                kind: var.kind(),
+                lower_bound: var.lower_bound(),
+                upper_bound: var.upper_bound(),
+                entries: vec![],
+            });
+        }
+        let mut rows = vec![];
+        let mut add_row = |row: Row, terms: &LinearExpression| {
+            let row_idx = rows.len();
+            for (name, coef) in terms.terms() {
+                let idx = *indices.entry(name.to_string()).or_insert_with(|| {
+                    // variables that are not declared get the default bounds of the format
+                    columns.push(Column {
+                        name: name.to_string(),
+                        kind: VariableKind::Continuous,
+                        lower_bound: 0.,
+                        upper_bound: f64::INFINITY,
+                        entries: vec![],
+                    });
+                    columns.len() - 1
+                });
+                columns[idx].entries.push((row_idx, coef));
+            }
+            rows.push(row);
+        };
+
+        let objective = terms_of(&problem.objective(), "objective")?;
+        add_row(
+            Row {
+                name: OBJECTIVE_ROW.to_string(),
+                kind: "N",
+                // the right hand side of the objective row is the opposite of its constant
+                rhs: -objective.constant() - problem.objective_offset(),
+                range: None,
+            },
+            &objective,
+        );
+        let mut names = mps_row_names(problem).into_iter();
+        let mut next_name = || names.next().unwrap_or_default();
+        for constraint in problem.constraints() {
+            let (row, lhs) = constraint_row(&constraint, next_name())?;
+            add_row(row, &lhs);
+        }
+        for range in problem.ranges() {
+            let name = next_name();
+            let lhs = terms_of(&range.lhs, &format!("constraint {}", name))?;
+            let row = match range.single_side() {
+                Some((operator, rhs)) => Row {
+                    name,
+                    kind: row_kind(operator),
+                    rhs: rhs - lhs.constant(),
+                    range: None,
+                },
+                None => Row {
+                    name,
+                    kind: "G",
+                    rhs: range.lower - lhs.constant(),
+                    range: Some(range.upper - range.lower),
+                },
+            };
+            add_row(row, &lhs);
+        }
+        for constraint in big_m_constraints(problem)
+            .map_err(invalid)?
+            .iter()
+            .flatten()
+        {
+            add_row(
+                Row {
+                    name: next_name(),
+                    kind: row_kind(constraint.operator),
+                    rhs: constraint.rhs,
+                    range: None,
+                },
+                &constraint.lhs,
+            );
+        }
+        // lazy constraints and user cuts are ordinary rows in the MPS format
+        for constraint in problem.lazy_constraints().chain(problem.user_cuts()) {
+            let (row, lhs) = constraint_row(&constraint, next_name())?;
+            add_row(row, &lhs);
+        }
+        Ok(MpsModel { rows, columns })
+    }
+
+    fn write<'a, P: LpProblem<'a>, W: Write>(
+        &self,
+        problem: &'a P,
+        w: &mut MpsWriter<W>,
+    ) -> io::Result<()> {
+        w.header("NAME", problem.name())?;
+        if problem.sense() == LpObjective::Maximize {
+            w.header("OBJSENSE", "")?;
+            w.line("", &["MAX"])?;
+        }
+
+        w.header("ROWS", "")?;
+        for row in &self.rows {
+            w.line(row.kind, &[&row.name])?;
+        }
+
+        w.header("COLUMNS", "")?;
+        let mut in_integer_block = false;
+        for column in &self.columns {
+            if column.kind.is_integer() != in_integer_block {
+                let marker = if column.kind.is_integer() {
+                    "'INTORG'"
+                } else {
+                    "'INTEND'"
+                };
+                w.line("", &["MARKER", "'MARKER'", "", marker])?;
+                in_integer_block = column.kind.is_integer();
+            }
+            if column.entries.is_empty() {
+                // a column must appear in this section to exist
+                w.line("", &[&column.name, OBJECTIVE_ROW, &w.number(0.)?])?;
+            }
+            for pair in column.entries.chunks(2) {
+                let mut fields = vec![column.name.clone()];
+                for &(row, coef) in pair {
+                    fields.push(self.rows[row].name.clone());
+                    fields.push(w.number(coef)?);
+                }
+                w.line("", &fields.iter().map(String::as_str).collect::<Vec<_>>())?;
+            }
+        }
+        if in_integer_block {
+            w.line("", &["MARKER", "'MARKER'", "", "'INTEND'"])?;
+        }
+
+        w.header("RHS", "")?;
+        for row in self.rows.iter().filter(|r| r.rhs != 0.) {
+            w.line("", &["RHS", &row.name, &w.number(row.rhs)?])?;
+        }
+
+        if self.rows.iter().any(|r| r.range.is_some()) {
+            w.header("RANGES", "")?;
+            for row in &self.rows {
+                if let Some(range) = row.range {
+                    w.line("", &["RNG", &row.name, &w.number(range)?])?;
+                }
+            }
+        }
+
+        w.header("BOUNDS", "")?;
+        for column in &self.columns {
+            for (kind, value) in bounds(column) {
+                let mut fields = vec!["BND".to_string(), column.name.clone()];
+                if let Some(v) = value {
+                    fields.push(w.number(v)?);
+                }
+                w.line(kind, &fields.iter().map(String::as_str).collect::<Vec<_>>())?;
+            }
+        }
+        write_sos(problem, w)?;
+        w.header("ENDATA", "")
+    }
+}
+
+/// Each set starts with a line giving its type, name and priority,
+/// followed by one line per variable with its weight
+fn write_sos<'a, P: LpProblem<'a>, W: Write>(
+    problem: &'a P,
+    w: &mut MpsWriter<W>,
+) -> io::Result<()> {
+    let names = sos_names(problem);
+    if names.is_empty() {
+        return Ok(());
+    }
+    w.header("SOS", "")?;
+    for (name, sos) in names.iter().zip(problem.sos_constraints()) {
+        let kind = match sos.kind {
+            SosType::S1 => "S1",
+            SosType::S2 => "S2",
+        };
+        let priority = sos.priority.map(|p| p.to_string()).unwrap_or_default();
+        w.line(kind, &["SOS", name, &priority])?;
+        for (variable, weight) in &sos.variables {
+            w.line("", &[variable, &w.number(*weight)?])?;
+        }
+    }
+    Ok(())
+}
+
+fn row_kind(operator: Ordering) -> &'static str {
+    match operator {
+        Ordering::Less => "L",
+        Ordering::Equal => "E",
+        Ordering::Greater => "G",
+    }
+}
+
+/// The BOUNDS records needed to give a column its bounds.
+/// Columns default to `0 <= x < +inf`.
+fn bounds(column: &Column) -> Vec<(&'static str, Option<f64>)> {
+    let (low, up) = (column.lower_bound, column.upper_bound);
+    if column.kind.is_semi() {
+        let mut records = vec![];
+        if low != 0. {
+            records.push(("LO", Some(low)));
+        }
+        // the SC bound is the upper bound of the variable when it is not 0, or none at all
+        records.push(("SC", Some(up).filter(|up| up.is_finite())));
+        return records;
+    }
+    if column.kind.is_integer() && low == 0. && up == 1. {
+        return vec![("BV", None)];
+    }
+    if low == up {
+        return vec![("FX", Some(low))];
+    }
+    let mut records = vec![];
+    match (low.is_finite(), up.is_finite()) {
+        (false, false) => records.push(("FR", None)),
+        (false, true) => records.push(("MI", None)),
+        // some readers make the lower bound -inf when the upper bound is negative
+        _ if low != 0. || up < 0. => records.push(("LO", Some(low))),
+        _ => {}
+    }
+    if up.is_finite() {
+        records.push(("UP", Some(up)));
+    } else if column.kind.is_integer() && low.is_finite() {
+        // some readers make integer columns binary by default
+        records.push(("PL", None));
+    }
+    records
+}
+
+struct MpsWriter<W> {
+    out: W,
+    format: MpsFormat,
+}
+
+impl<W: Write> MpsWriter<W> {
+    /// Section headers start at the first column
+    fn header(&mut self, name: &str, value: &str) -> io::Result<()> {
+        if value.is_empty() {
+            writeln!(self.out, "{}", name)
+        } else if self.format == MpsFormat::Fixed {
+            writeln!(self.out, "{:<14}{}", name, value)
+        } else {
+            writeln!(self.out, "{} {}", name, value)
+        }
+    }
+
+    /// A data record: an optional code, followed by name and value fields
+    fn line(&mut self, code: &str, fields: &[&str]) -> io::Result<()> {
+        match self.format {
+            MpsFormat::Free => {
+                let mut line = format!(" {:<2}", code);
+                for field in fields.iter().filter(|f| !f.is_empty()) {
+                    check_free_name(field)?;
+                    line.push(' ');
+                    line.push_str(field);
+                }
+                writeln!(self.out, "{}", line.trim_end())
+            }
+            MpsFormat::Fixed => {
+                // fields start at columns 2, 5, 15, 25, 40 and 50
+                const WIDTHS: [usize; 5] = [8, 8, 12, 8, 12];
+                const SEPARATORS: [usize; 5] = [2, 2, 3, 2, 0];
+                let mut line = format!(" {:<2} ", code);
+                for (i, field) in fields.iter().enumerate() {
+                    if field.chars().count() > WIDTHS[i] || field.contains(' ') {
+                        return Err(invalid(format!(
+                            "{:?} does not fit in a {} character field of the fixed MPS format",
+                            field, WIDTHS[i]
+                        )));
+                    }
+                    line.push_str(&format!("{:<1$}", field, WIDTHS[i] + SEPARATORS[i]));
+                }
+                writeln!(self.out, "{}", line.trim_end())
+            }
+        }
+    }
+
+    fn number(&self, value: f64) -> io::Result<String> {
+        let text = value.to_string();
+        if self.format == MpsFormat::Free || text.len() <= 12 {
+            return Ok(text);
+        }
+        // keep the representation that fits in the field and loses the least precision
+        let error = |t: &String| (t.parse::<f64>().unwrap_or(f64::NAN) - value).abs();
+        (0..12)
+            .flat_map(|precision| {
+                vec![
+                    format!("{:.*}", precision, value),
+                    format!("{:.*e}", precision, value),
+                ]
+            })
+            .filter(|t| t.len() <= 12)
+            .min_by(|a, b| error(a).total_cmp(&error(b)))
+            .ok_or_else(|| invalid(format!("{} does not fit in the fixed MPS format", value)))
+    }
+}
+
+fn check_free_name(name: &str) -> io::Result<()> {
+    if name.contains(char::is_whitespace) {
+        Err(invalid(format!(
+            "{:?} is not a valid name in the free MPS format",
+            name
+        )))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,641 @@
+//! Read problems written in the MPS format.
+//!
+//! Both the fixed and the free variants of the format are supported, with the `OBJSENSE`
+//! and `OBJNAME` extensions, `RANGES`, integer markers and all the usual bound types.
+//!
+//! ```
+//! use lp_solvers::lp_format::LpObjective;
+//! use lp_solvers::mps_format::MpsFormat;
+//! use lp_solvers::mps_reader::parse_mps;
+//!
+//! let problem = parse_mps("NAME example
+//! OBJSENSE
+//!     MAX
+//! ROWS
+//!  N  profit
+//!  L  limit
+//! COLUMNS
+//!     x  profit  1  limit  1
+//!     y  profit  2  limit  1
+//! RHS
+//!     RHS  limit  10
+//! BOUNDS
+//!  UP BND  y  4
+//! ENDATA
+//! ", MpsFormat::Free).unwrap();
+//! assert_eq!(problem.sense, LpObjective::Maximize);
+//! assert_eq!(problem.objective.to_string(), "x + 2 y");
+//! assert_eq!(problem.variables[1].upper_bound, 4.);
+//! ```
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::lp_format::{
+    Constraint, LpObjective, RangeConstraint, SosConstraint, SosType, VariableKind,
+};
+use crate::lp_reader::ParseError;
+use crate::mps_format::MpsFormat;
+use crate::problem::{LinearExpression, Problem, Variable};
+
+/// Read an MPS file from disk. See [parse_mps].
+///
+/// Files whose name ends with `.gz` are decompressed on the fly
+/// when the `gzip` feature of this crate is enabled.
+pub fn read_mps_file(path: &Path, format: MpsFormat) -> Result<Problem, String> {
+    let content =
+        read_to_string(path).map_err(|e| format!("Cannot open problem file {:?}: {}", path, e))?;
+    parse_mps(&content, format).map_err(|e| format!("Invalid problem file {:?}: {}", path, e))
+}
+
+#[cfg(feature = "gzip")]
+fn read_to_string(path: &Path) -> std::io::Result<String> {
+    use std::io::Read;
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        let mut content = String::new();
+        flate2::read::GzDecoder::new(std::fs::File::open(path)?).read_to_string(&mut content)?;
+        Ok(content)
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
+
+#[cfg(not(feature = "gzip"))]
+fn read_to_string(path: &Path) -> std::io::Result<String> {
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "reading compressed files requires the \"gzip\" feature",
+        ));
+    }
+    std::fs::read_to_string(path)
+}
+
+/// Parse a problem in the MPS format.
+///
+/// The first `N` row is the objective, unless another one is selected in an `OBJNAME` section.
+/// The right hand side of the objective row is the opposite of the objective constant.
+/// Ranged rows become [RangeConstraint]s.
+pub fn parse_mps(content: &str, format: MpsFormat) -> Result<Problem, ParseError> {
+    let mut reader = MpsReader::new(format);
+    for (idx, line) in content.lines().enumerate() {
+        reader.line_nr = idx + 1;
+        if line.trim().is_empty() || line.starts_with('*') {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace) {
+            reader.record(line)?;
+        } else if reader.header(line)? {
+            return reader.finish();
+        }
+    }
+    Err(ParseError::new(
+        reader.line_nr,
+        1,
+        "unexpected end of file, expected ENDATA",
+    ))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Section {
+    Start,
+    ObjSense,
+    ObjName,
+    Rows,
+    Columns,
+    Rhs,
+    Ranges,
+    Bounds,
+    Sos,
+}
+
+/// A field of a data record, with its 1-based column
+#[derive(Clone, Copy, Debug)]
+struct Field<'l> {
+    text: &'l str,
+    column: usize,
+}
+
+/// Cut a fixed format line at columns 2-3, 5-12, 15-22, 25-36, 40-47 and 50-61
+fn fixed_fields(line: &str) -> [Option<Field<'_>>; 6] {
+    const POSITIONS: [(usize, usize); 6] =
+        [(1, 3), (4, 12), (14, 22), (24, 36), (39, 47), (49, 61)];
+    let mut fields = [None; 6];
+    for (field, &(start, end)) in fields.iter_mut().zip(POSITIONS.iter()) {
+        let text = line.get(start..end.min(line.len())).unwrap_or("");
+        let trimmed = text.trim_start();
+        let column = start + 1 + text.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        if !trimmed.is_empty() {
+            *field = Some(Field {
+                text: trimmed,
+                column,
+            });
+        }
+    }
+    fields
+}
+
+fn free_fields(line: &str) -> Vec<Field<'_>> {
+    let mut fields = vec![];
+    let mut start = None;
+    for (i, c) in line
+        .char_indices()
+        .chain(std::iter::once((line.len(), ' ')))
+    {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                fields.push(Field {
+                    text: &line[s..i],
+                    column: s + 1,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    fields
+}
+
+fn is_sos_header(field: &Field) -> bool {
+    field.text.eq_ignore_ascii_case("S1") || field.text.eq_ignore_ascii_case("S2")
+}
+
+struct Row {
+    name: String,
+    operator: Option<Ordering>,
+    terms: LinearExpression,
+    rhs: f64,
+    range: Option<f64>,
+}
+
+struct MpsReader {
+    format: MpsFormat,
+    line_nr: usize,
+    section: Section,
+    name: String,
+    sense: LpObjective,
+    objective_name: Option<String>,
+    rows: Vec<Row>,
+    row_indices: HashMap<String, usize>,
+    variables: Vec<Variable>,
+    variable_indices: HashMap<String, usize>,
+    /// variables whose lower bound was set explicitly
+    explicit_lower: Vec<bool>,
+    in_integer_block: bool,
+    sos: Vec<SosConstraint>,
+}
+
+impl MpsReader {
+    fn new(format: MpsFormat) -> Self {
+        MpsReader {
+            format,
+            line_nr: 0,
+            section: Section::Start,
+            name: "lp_solvers_problem".to_string(),
+            sense: LpObjective::Minimize,
+            objective_name: None,
+            rows: vec![],
+            row_indices: HashMap::new(),
+            variables: vec![],
+            variable_indices: HashMap::new(),
+            explicit_lower: vec![],
+            in_integer_block: false,
+            sos: vec![],
+        }
+    }
+
+    fn error(&self, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.line_nr, column, message)
+    }
+
+    fn field_error(&self, field: &Field, message: impl Into<String>) -> ParseError {
+        self.error(field.column, message)
+    }
+
+    /// Handle a section header. Returns true at the end of the data
+    fn header(&mut self, line: &str) -> Result<bool, ParseError> {
+        let fields = free_fields(line);
+        let keyword = fields[0].text.to_ascii_uppercase();
+        let argument = fields.get(1);
+        self.section = match keyword.as_str() {
+            "NAME" => {
+                let name = match self.format {
+                    MpsFormat::Fixed => line.get(14..).unwrap_or("").trim(),
+                    MpsFormat::Free => line[4..].trim(),
+                };
+                if !name.is_empty() {
+                    self.name = name.to_string();
+                }
+                return Ok(false);
+            }
+            "OBJSENSE" => match argument {
+                Some(arg) => {
+                    self.set_sense(arg)?;
+                    Section::Start
+                }
+                None => Section::ObjSense,
+            },
+            "OBJNAME" => match argument {
+                Some(arg) => {
+                    self.objective_name = Some(arg.text.to_string());
+                    Section::Start
+                }
+                None => Section::ObjName,
+            },
+            "ROWS" => Section::Rows,
+            "COLUMNS" => Section::Columns,
+            "RHS" => Section::Rhs,
+            "RANGES" => Section::Ranges,
+            "BOUNDS" => Section::Bounds,
+            "SOS" => Section::Sos,
+            "ENDATA" => return Ok(true),
+            _ => {
+                return Err(self.field_error(
+                    &fields[0],
+                    format!("unsupported section {}", fields[0].text),
+                ))
+            }
+        };
+        Ok(false)
+    }
+
+    fn set_sense(&mut self, field: &Field) -> Result<(), ParseError> {
+        self.sense = match field.text.to_ascii_uppercase().as_str() {
+            "MAX" | "MAXIMIZE" | "MAXIMISE" => LpObjective::Maximize,
+            "MIN" | "MINIMIZE" | "MINIMISE" => LpObjective::Minimize,
+            _ => {
+                return Err(
+                    self.field_error(field, format!("invalid objective sense {}", field.text))
+                )
+            }
+        };
+        Ok(())
+    }
+
+    /// Split a data record into the six fields of the fixed format:
+    /// code, name, name, number, name, number
+    fn fields<'l>(&self, line: &'l str) -> Result<[Option<Field<'l>>; 6], ParseError> {
+        if self.format == MpsFormat::Fixed {
+            return Ok(fixed_fields(line));
+        }
+        let free = free_fields(line);
+        let mut fields = [None; 6];
+        let mut place = |positions: &[usize]| {
+            for (&pos, field) in positions.iter().zip(free.iter()) {
+                fields[pos] = Some(*field);
+            }
+        };
+        let max_fields = match self.section {
+            Section::Rows => 2,
+            Section::Bounds | Section::Sos => 4,
+            _ => 5,
+        };
+        match self.section {
+            Section::Rows | Section::ObjSense | Section::ObjName => place(&[0, 1]),
+            Section::Columns if free.iter().any(|f| f.text == "'MARKER'") => place(&[1, 2, 4]),
+            Section::Columns => place(&[1, 2, 3, 4, 5]),
+            // the name of the vector is optional in the free format
+            Section::Rhs | Section::Ranges if matches!(free.len(), 2 | 4) => place(&[2, 3, 4, 5]),
+            Section::Rhs | Section::Ranges => place(&[1, 2, 3, 4, 5]),
+            Section::Bounds => {
+                let has_value = match free[0].text.to_ascii_uppercase().as_str() {
+                    "FR" | "MI" | "PL" | "BV" => false,
+                    // the value of semi-continuous bounds is optional
+                    "SC" => free.len() == 4 || free[free.len() - 1].text.parse::<f64>().is_ok(),
+                    _ => true,
+                };
+                match (free.len(), has_value) {
+                    (3, true) | (2, false) => place(&[0, 2, 3]),
+                    _ => place(&[0, 1, 2, 3]),
+                }
+            }
+            Section::Sos if is_sos_header(&free[0]) => place(&[0, 1, 2, 3]),
+            Section::Sos => place(&[1, 2]),
+            Section::Start => {}
+        }
+        if free.len() > max_fields {
+            return Err(self.field_error(&free[free.len() - 1], "too many fields in record"));
+        }
+        Ok(fields)
+    }
+
+    fn record(&mut self, line: &str) -> Result<(), ParseError> {
+        let fields = self.fields(line)?;
+        match self.section {
+            Section::Start => Err(self.error(1, "data record outside of any section")),
+            Section::ObjSense => {
+                let field = fields
+                    .iter()
+                    .flatten()
+                    .next()
+                    .expect("the line is not empty");
+                self.set_sense(field)
+            }
+            Section::ObjName => {
+                let field = fields
+                    .iter()
+                    .flatten()
+                    .next()
+                    .expect("the line is not empty");
+                self.objective_name = Some(field.text.to_string());
+                Ok(())
+            }
+            Section::Rows => self.row(&fields),
+            Section::Columns => self.column(&fields),
+            Section::Rhs | Section::Ranges => {
+                self.required(&fields, 2, "a row name")?;
+                for (name_pos, value_pos) in [(2, 3), (4, 5)] {
+                    if let Some(name) = fields[name_pos] {
+                        let value = self.number(&fields, value_pos)?;
+                        let row = self.row_index(&name)?;
+                        if self.section == Section::Rhs {
+                            self.rows[row].rhs = value;
+                        } else {
+                            self.rows[row].range = Some(value);
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Section::Bounds => self.bound(&fields),
+            Section::Sos => self.sos_record(&fields),
+        }
+    }
+
+    /// Either the header of a set (`S1 SOS name priority`), or a variable and its weight
+    fn sos_record(&mut self, fields: &[Option<Field>; 6]) -> Result<(), ParseError> {
+        if let Some(code) = fields[0] {
+            let kind = match code.text.to_ascii_uppercase().as_str() {
+                "S1" => SosType::S1,
+                "S2" => SosType::S2,
+                _ => return Err(self.field_error(&code, format!("invalid set type {}", code.text))),
+            };
+            let priority = match fields[3] {
+                Some(field) => Some(field.text.parse().map_err(|_| {
+                    self.field_error(&field, format!("invalid priority {}", field.text))
+                })?),
+                None => None,
+            };
+            self.sos.push(SosConstraint {
+                name: fields[2].map(|f| f.text.to_string()),
+                kind,
+                variables: vec![],
+                priority,
+            });
+            return Ok(());
+        }
+        let variable = self.required(fields, 1, "a column name")?;
+        let weight = self.number(fields, 2)?;
+        match self.sos.last_mut() {
+            Some(set) => {
+                set.variables.push((variable.text.to_string(), weight));
+                Ok(())
+            }
+            None => Err(self.field_error(&variable, "set member before any set header")),
+        }
+    }
+
+    fn required<'l>(
+        &self,
+        fields: &[Option<Field<'l>>; 6],
+        pos: usize,
+        expected: &str,
+    ) -> Result<Field<'l>, ParseError> {
+        fields[pos].ok_or_else(|| {
+            let column = match self.format {
+                MpsFormat::Fixed => [2, 5, 15, 25, 40, 50][pos],
+                MpsFormat::Free => fields.iter().flatten().last().map_or(1, |f| f.column),
+            };
+            self.error(column, format!("missing field, expected {}", expected))
+        })
+    }
+
+    fn number(&self, fields: &[Option<Field>; 6], pos: usize) -> Result<f64, ParseError> {
+        let field = self.required(fields, pos, "a number")?;
+        let value: f64 = field
+            .text
+            .parse()
+            .map_err(|_| self.field_error(&field, format!("invalid number {}", field.text)))?;
+        // 1e30 is the conventional infinity of MPS files
+        Ok(if value.abs() >= 1e30 {
+            value.signum() * f64::INFINITY
+        } else {
+            value
+        })
+    }
+
+    fn row(&mut self, fields: &[Option<Field>; 6]) -> Result<(), ParseError> {
+        let code = self.required(fields, 0, "a row type")?;
+        let name = self.required(fields, 1, "a row name")?;
+        let operator = match code.text.to_ascii_uppercase().as_str() {
+            "N" => None,
+            "L" => Some(Ordering::Less),
+            "G" => Some(Ordering::Greater),
+            "E" => Some(Ordering::Equal),
+            _ => {
+                return Err(self.field_error(&code, format!("invalid row type {}", code.text)));
+            }
+        };
+        if self
+            .row_indices
+            .insert(name.text.to_string(), self.rows.len())
+            .is_some()
+        {
+            return Err(self.field_error(&name, format!("duplicate row {}", name.text)));
+        }
+        self.rows.push(Row {
+            name: name.text.to_string(),
+            operator,
+            terms: LinearExpression::default(),
+            rhs: 0.,
+            range: None,
+        });
+        Ok(())
+    }
+
+    fn row_index(&self, name: &Field) -> Result<usize, ParseError> {
+        self.row_indices
+            .get(name.text)
+            .copied()
+            .ok_or_else(|| self.field_error(name, format!("unknown row {}", name.text)))
+    }
+
+    fn column(&mut self, fields: &[Option<Field>; 6]) -> Result<(), ParseError> {
+        if fields[2].is_some_and(|f| f.text == "'MARKER'") {
+            let marker = self.required(fields, 4, "'INTORG' or 'INTEND'")?;
+            self.in_integer_block = match marker.text {
+                "'INTORG'" => true,
+                "'INTEND'" => false,
+                other => {
+                    return Err(self.field_error(&marker, format!("invalid marker {}", other)));
+                }
+            };
+            return Ok(());
+        }
+        let name = self.required(fields, 1, "a column name")?;
+        let idx = self.variable(name.text);
+        if self.in_integer_block {
+            self.variables[idx].kind = VariableKind::Integer;
+        }
+        self.required(fields, 2, "a row name")?;
+        for (row_pos, value_pos) in [(2, 3), (4, 5)] {
+            if let Some(row) = fields[row_pos] {
+                let value = self.number(fields, value_pos)?;
+                let row = self.row_index(&row)?;
+                self.rows[row].terms.add_term(name.text, value);
+            }
+        }
+        Ok(())
+    }
+
+    fn variable(&mut self, name: &str) -> usize {
+        let variables = &mut self.variables;
+        let explicit_lower = &mut self.explicit_lower;
+        *self
+            .variable_indices
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                variables.push(Variable {
+                    name: name.to_string(),
+                    kind: VariableKind::Continuous,
+                    lower_bound: 0.,
+                    upper_bound: f64::INFINITY,
+                });
+                explicit_lower.push(false);
+                variables.len() - 1
+            })
+    }
+
+    fn bound(&mut self, fields: &[Option<Field>; 6]) -> Result<(), ParseError> {
+        let code = self.required(fields, 0, "a bound type")?;
+        let name = self.required(fields, 2, "a column name")?;
+        let idx = *self
+            .variable_indices
+            .get(name.text)
+            .ok_or_else(|| self.field_error(&name, format!("unknown column {}", name.text)))?;
+        let code_text = code.text.to_ascii_uppercase();
+        let value = match code_text.as_str() {
+            "FR" | "MI" | "PL" | "BV" => 0.,
+            "SC" if fields[3].is_none() => f64::INFINITY,
+            _ => self.number(fields, 3)?,
+        };
+        let var = &mut self.variables[idx];
+        match code_text.as_str() {
+            "UP" | "UI" => {
+                var.upper_bound = value;
+                // an upper bound below the default lower bound makes the variable unbounded below
+                if value < 0. && !self.explicit_lower[idx] && var.lower_bound == 0. {
+                    var.lower_bound = f64::NEG_INFINITY;
+                }
+            }
+            "LO" | "LI" => var.lower_bound = value,
+            "FX" => {
+                var.lower_bound = value;
+                var.upper_bound = value;
+            }
+            "FR" => {
+                var.lower_bound = f64::NEG_INFINITY;
+                var.upper_bound = f64::INFINITY;
+            }
+            "MI" => var.lower_bound = f64::NEG_INFINITY,
+            "PL" => var.upper_bound = f64::INFINITY,
+            "BV" => {
+                var.lower_bound = 0.;
+                var.upper_bound = 1.;
+            }
+            "SC" => {
+                // a zero bound means that the variable has no upper bound
+                var.upper_bound = if value == 0. { f64::INFINITY } else { value };
+                var.kind = if var.kind.is_integer() {
+                    VariableKind::SemiInteger
+                } else {
+                    VariableKind::SemiContinuous
+                };
+            }
+            _ => {
+                return Err(self.field_error(&code, format!("invalid bound type {}", code.text)));
+            }
+        }
+        match code_text.as_str() {
+            "UI" | "LI" if var.kind == VariableKind::SemiContinuous => {
+                var.kind = VariableKind::SemiInteger
+            }
+            "UI" | "LI" if !var.kind.is_integer() => var.kind = VariableKind::Integer,
+            "BV" => var.kind = VariableKind::Binary,
+            _ => {}
+        }
+        if matches!(code_text.as_str(), "LO" | "LI" | "FX" | "FR" | "MI" | "BV") {
+            self.explicit_lower[idx] = true;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Problem, ParseError> {
+        let objective_idx = match &self.objective_name {
+            Some(name) => self
+                .row_indices
+                .get(name)
+                .copied()
+                .filter(|&i| self.rows[i].operator.is_none()),
+            None => self.rows.iter().position(|r| r.operator.is_none()),
+        };
+        let objective = match objective_idx {
+            Some(idx) => {
+                let row = &self.rows[idx];
+                let mut terms = row.terms.clone();
+                terms.set_constant(-row.rhs);
+                terms
+            }
+            None if self.objective_name.is_some() => {
+                return Err(self.error(1, "the objective row named in OBJNAME does not exist"))
+            }
+            None => LinearExpression::default(),
+        };
+        let mut constraints = vec![];
+        let mut ranges = vec![];
+        for row in &self.rows {
+            let operator = match row.operator {
+                Some(o) => o,
+                None => continue,
+            };
+            let (lower, upper) = match (operator, row.range) {
+                (_, None) | (Ordering::Equal, Some(0.)) => {
+                    constraints.push(Constraint {
+                        lhs: row.terms.clone(),
+                        operator,
+                        rhs: row.rhs,
+                        name: Some(row.name.clone()),
+                    });
+                    continue;
+                }
+                (Ordering::Equal, Some(r)) if r < 0. => (row.rhs + r, row.rhs),
+                (Ordering::Equal, Some(r)) => (row.rhs, row.rhs + r),
+                (Ordering::Less, Some(r)) => (row.rhs - r.abs(), row.rhs),
+                (Ordering::Greater, Some(r)) => (row.rhs, row.rhs + r.abs()),
+            };
+            ranges.push(RangeConstraint {
+                lhs: row.terms.clone(),
+                lower,
+                upper,
+                name: Some(row.name.clone()),
+            });
+        }
+        Ok(Problem {
+            name: self.name,
+            sense: self.sense,
+            objective,
+            variables: self.variables,
+            constraints,
+            ranges,
+            sos: self.sos,
+            indicators: vec![],
+            general_constraints: vec![],
+            lazy_constraints: vec![],
+            user_cuts: vec![],
+            objective_offset: 0.,
+            objectives: vec![],
+        })
+    }
+}
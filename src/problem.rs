@@ -1,28 +1,315 @@
 //! Concrete implementations for the traits in [crate::lp_format]
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Formatter;
 
-use crate::lp_format::{AsVariable, Constraint, LpObjective, LpProblem, WriteToLpFileFormat};
+use crate::lp_format::{
+    lp_string, objective_lp_string, AsVariable, Constraint, GeneralConstraint, IndicatorConstraint,
+    LpNumber, LpObjective, LpProblem, Objective, RangeConstraint, SosConstraint, VariableKind,
+    WriteToLpFileFormat,
+};
 
 /// A string that is a valid expression in the .lp format for the solver you are using
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct StrExpression(pub String);
 
+/// A linear combination of variables, plus a constant.
+///
+/// Terms on the same variable are merged, and terms whose coefficient is zero are dropped.
+///
+/// ```
+/// use lp_solvers::problem::LinearExpression;
+///
+/// let mut expr = LinearExpression::new(vec![("x", 2.), ("y", -1.), ("x", 1.)], 5.);
+/// expr.add_term("y", 1.);
+/// assert_eq!(expr.coefficient("x"), 3.);
+/// assert_eq!(expr.terms().collect::<Vec<_>>(), vec![("x", 3.)]);
+/// assert_eq!(expr.to_string(), "3 x + 5");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LinearExpression {
+    /// coefficients in order of first appearance. May contain zeros, that are never exposed
+    terms: Vec<(String, f64)>,
+    positions: HashMap<String, usize>,
+    constant: f64,
+}
+
+impl LinearExpression {
+    /// Create an expression from (variable name, coefficient) pairs and a constant
+    pub fn new<S: AsRef<str>>(terms: impl IntoIterator<Item = (S, f64)>, constant: f64) -> Self {
+        let mut expr = LinearExpression {
+            constant,
+            ..Default::default()
+        };
+        for (name, coefficient) in terms {
+            expr.add_term(name.as_ref(), coefficient);
+        }
+        expr
+    }
+
+    /// Add `coefficient * name` to the expression
+    pub fn add_term(&mut self, name: &str, coefficient: f64) {
+        match self.positions.get(name) {
+            Some(&idx) => self.terms[idx].1 += coefficient,
+            None => {
+                self.positions.insert(name.to_string(), self.terms.len());
+                self.terms.push((name.to_string(), coefficient));
+            }
+        }
+    }
+
+    /// The non-zero terms of the expression, in order of first appearance
+    pub fn terms(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.terms
+            .iter()
+            .filter(|(_, c)| *c != 0.)
+            .map(|(name, c)| (name.as_str(), *c))
+    }
+
+    /// The coefficient of the given variable, 0 if it does not appear in the expression
+    pub fn coefficient(&self, name: &str) -> f64 {
+        self.positions
+            .get(name)
+            .map_or(0., |&idx| self.terms[idx].1)
+    }
+
+    /// The constant part of the expression
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+
+    /// Replace the constant part of the expression
+    pub fn set_constant(&mut self, constant: f64) {
+        self.constant = constant;
+    }
+
+    /// Value of the expression for the given variable values.
+    /// Missing variables are considered to be zero.
+    pub fn evaluate(&self, values: &HashMap<String, f32>) -> f64 {
+        self.terms()
+            .map(|(name, c)| c * values.get(name).copied().unwrap_or(0.) as f64)
+            .sum::<f64>()
+            + self.constant
+    }
+}
+
+/// Expressions are equal when they have the same non-zero terms, in any order
+impl PartialEq for LinearExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.constant == other.constant
+            && self.terms().count() == other.terms().count()
+            && self.terms().all(|(name, c)| other.coefficient(name) == c)
+    }
+}
+
+impl WriteToLpFileFormat for LinearExpression {
+    fn is_quadratic(&self) -> bool {
+        false
+    }
+
+    fn to_lp_file_format(&self, f: &mut Formatter) -> fmt::Result {
+        let mut first = true;
+        for (name, coefficient) in self.terms() {
+            let sign = if coefficient < 0. { "-" } else { "+" };
+            if first {
+                if coefficient < 0. {
+                    f.write_str("-")?;
+                }
+            } else {
+                write!(f, " {} ", sign)?;
+            }
+            if coefficient.abs() != 1. {
+                write!(f, "{} ", LpNumber(coefficient.abs()))?;
+            }
+            f.write_str(name)?;
+            first = false;
+        }
+        if first {
+            write!(f, "{}", LpNumber(self.constant))
+        } else if self.constant != 0. {
+            let sign = if self.constant < 0. { "-" } else { "+" };
+            write!(f, " {} {}", sign, LpNumber(self.constant.abs()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for LinearExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_lp_file_format(f)
+    }
+}
+
+/// A linear expression plus a sum of products of two variables.
+///
+/// In objectives, the quadratic part is written as `[ ... ] / 2`, with doubled coefficients.
+///
+/// ```
+/// use lp_solvers::lp_format::WriteToLpFileFormat;
+/// use lp_solvers::problem::{LinearExpression, QuadraticExpression};
+///
+/// let mut expr = QuadraticExpression::from(LinearExpression::new(vec![("x", 1.)], 0.));
+/// expr.add_quadratic_term("x", "x", 1.5);
+/// expr.add_quadratic_term("x", "y", -1.);
+/// expr.add_quadratic_term("y", "x", -1.);
+/// assert_eq!(expr.to_string(), "x + [ 1.5 x ^ 2 - 2 x * y ]");
+/// assert!(expr.is_quadratic());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuadraticExpression {
+    /// The linear terms and the constant
+    pub linear: LinearExpression,
+    /// non-zero coefficients of the products of two variables, keyed by their sorted names
+    quadratic: HashMap<(String, String), f64>,
+}
+
+impl QuadraticExpression {
+    /// Add `coefficient * a * b` to the expression. `a * b` and `b * a` are the same term.
+    pub fn add_quadratic_term(&mut self, a: &str, b: &str, coefficient: f64) {
+        let (a, b) = if a <= b { (a, b) } else { (b, a) };
+        let key = (a.to_string(), b.to_string());
+        let sum = self.quadratic.get(&key).copied().unwrap_or(0.) + coefficient;
+        if sum == 0. {
+            self.quadratic.remove(&key);
+        } else {
+            self.quadratic.insert(key, sum);
+        }
+    }
+
+    /// The constraint `x1^2 + x2^2 + ... <= t^2`.
+    /// With a non-negative lower bound on `t`, it is a second-order cone constraint:
+    /// the norm of `(x1, x2, ...)` is at most `t`.
+    ///
+    /// ```
+    /// use lp_solvers::problem::QuadraticExpression;
+    ///
+    /// let cone = QuadraticExpression::second_order_cone("t", &["x", "y"]);
+    /// assert_eq!(cone.lhs.to_string(), "[ -t ^ 2 + x ^ 2 + y ^ 2 ]");
+    /// assert_eq!(cone.rhs, 0.);
+    /// ```
+    pub fn second_order_cone<S: AsRef<str>>(t: &str, xs: &[S]) -> Constraint<Self> {
+        let mut lhs = QuadraticExpression::default();
+        for x in xs {
+            lhs.add_quadratic_term(x.as_ref(), x.as_ref(), 1.);
+        }
+        lhs.add_quadratic_term(t, t, -1.);
+        Constraint {
+            lhs,
+            operator: Ordering::Less,
+            rhs: 0.,
+            name: None,
+        }
+    }
+
+    /// The non-zero quadratic terms of the expression, as (variable, variable, coefficient).
+    /// The two variables of a term are in sorted order, and so are the terms.
+    pub fn quadratic_terms(&self) -> impl Iterator<Item = (&str, &str, f64)> {
+        let mut terms: Vec<_> = self
+            .quadratic
+            .iter()
+            .map(|((a, b), &c)| (a.as_str(), b.as_str(), c))
+            .collect();
+        terms.sort_by(|x, y| (x.0, x.1).cmp(&(y.0, y.1)));
+        terms.into_iter()
+    }
+
+    /// Write the expression, with the quadratic coefficients multiplied by `factor`
+    fn write(&self, f: &mut Formatter, factor: f64, suffix: &str) -> fmt::Result {
+        let has_linear = self.linear.terms().next().is_some() || self.linear.constant() != 0.;
+        if !has_linear && !self.quadratic.is_empty() {
+            return self.write_quadratic(f, factor, suffix);
+        }
+        self.linear.to_lp_file_format(f)?;
+        if !self.quadratic.is_empty() {
+            f.write_str(" + ")?;
+            self.write_quadratic(f, factor, suffix)?;
+        }
+        Ok(())
+    }
+
+    fn write_quadratic(&self, f: &mut Formatter, factor: f64, suffix: &str) -> fmt::Result {
+        let products = self.quadratic_terms().map(|(a, b, coefficient)| {
+            let product = if a == b {
+                format!("{} ^ 2", a)
+            } else {
+                format!("{} * {}", a, b)
+            };
+            (product, coefficient * factor)
+        });
+        write!(f, "[ {} ]{}", LinearExpression::new(products, 0.), suffix)
+    }
+}
+
+impl From<LinearExpression> for QuadraticExpression {
+    fn from(linear: LinearExpression) -> Self {
+        QuadraticExpression {
+            linear,
+            quadratic: HashMap::new(),
+        }
+    }
+}
+
+impl WriteToLpFileFormat for QuadraticExpression {
+    fn to_lp_file_format(&self, f: &mut Formatter) -> fmt::Result {
+        self.write(f, 1., "")
+    }
+
+    fn objective_to_lp_file_format(&self, f: &mut Formatter) -> fmt::Result {
+        self.write(f, 2., " / 2")
+    }
+
+    fn is_quadratic(&self) -> bool {
+        !self.quadratic.is_empty()
+    }
+}
+
+impl fmt::Display for QuadraticExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_lp_file_format(f)
+    }
+}
+
 /// A variable to optimize
+#[derive(Debug, Clone, PartialEq)]
 pub struct Variable {
     /// The variable name should be unique in the problem and have a name accepted by the solver
     pub name: String,
-    /// Whether the variable is restricted to only integer values
-    pub is_integer: bool,
+    /// Whether the variable is continuous, integer, binary, ...
+    pub kind: VariableKind,
     /// -INFINITY if there is no lower bound
     pub lower_bound: f64,
     /// INFINITY if there is no upper bound
     pub upper_bound: f64,
 }
 
+impl Variable {
+    /// Create a variable. `kind` is a [VariableKind], or a `bool` telling whether the variable is
+    /// an integer, as the `is_integer` field of previous versions did.
+    pub fn new(
+        name: &str,
+        kind: impl Into<VariableKind>,
+        lower_bound: f64,
+        upper_bound: f64,
+    ) -> Self {
+        Variable {
+            name: name.to_string(),
+            kind: kind.into(),
+            lower_bound,
+            upper_bound,
+        }
+    }
+}
+
 impl WriteToLpFileFormat for StrExpression {
     fn to_lp_file_format(&self, f: &mut Formatter) -> fmt::Result {
         f.write_str(&self.0)
     }
+
+    fn is_quadratic(&self) -> bool {
+        self.0.contains('[')
+    }
 }
 
 impl AsVariable for Variable {
@@ -30,8 +317,8 @@ impl AsVariable for Variable {
         &self.name
     }
 
-    fn is_integer(&self) -> bool {
-        self.is_integer
+    fn kind(&self) -> VariableKind {
+        self.kind
     }
 
     fn lower_bound(&self) -> f64 {
@@ -43,8 +330,11 @@ impl AsVariable for Variable {
     }
 }
 
-/// A concrete linear problem
-pub struct Problem<EXPR = StrExpression, VAR = Variable> {
+/// A concrete linear problem.
+/// Create it with [Problem::new] or [Problem::default], and set the other fields afterwards.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Problem<EXPR = LinearExpression, VAR = Variable> {
     /// problem name. "lp_solvers_problem" by default
     /// Write the problem in the lp file format to the given formatter
     pub name: String,
@@ -56,6 +346,132 @@ pub struct Problem<EXPR = StrExpression, VAR = Variable> {
     pub variables: Vec<VAR>,
     /// List of constraints to apply
     pub constraints: Vec<Constraint<EXPR>>,
+    /// Two-sided constraints
+    pub ranges: Vec<RangeConstraint<EXPR>>,
+    /// Special ordered sets
+    pub sos: Vec<SosConstraint>,
+    /// Constraints that only apply when a binary variable has a given value
+    pub indicators: Vec<IndicatorConstraint<EXPR>>,
+    /// Constraints that set a variable to a function of other variables. Gurobi only.
+    pub general_constraints: Vec<GeneralConstraint>,
+    /// Constraints that the solver only checks when it finds a solution
+    pub lazy_constraints: Vec<Constraint<EXPR>>,
+    /// Constraints that are implied by the others, but help cut off fractional solutions
+    pub user_cuts: Vec<Constraint<EXPR>>,
+    /// A constant added to the objective
+    pub objective_offset: f64,
+    /// Objectives that replace `objective` when the problem has several
+    pub objectives: Vec<Objective<EXPR>>,
+}
+
+impl<EXPR, VAR> Problem<EXPR, VAR> {
+    /// A problem with only an objective, variables and constraints
+    pub fn new(
+        name: &str,
+        sense: LpObjective,
+        objective: EXPR,
+        variables: Vec<VAR>,
+        constraints: Vec<Constraint<EXPR>>,
+    ) -> Self {
+        Problem {
+            name: name.to_string(),
+            sense,
+            objective,
+            variables,
+            constraints,
+            ranges: vec![],
+            sos: vec![],
+            indicators: vec![],
+            general_constraints: vec![],
+            lazy_constraints: vec![],
+            user_cuts: vec![],
+            objective_offset: 0.,
+            objectives: vec![],
+        }
+    }
+}
+
+/// An empty problem, that minimizes the default expression.
+/// Useful to set only the fields you need:
+///
+/// ```
+/// use lp_solvers::problem::{Problem, StrExpression};
+///
+/// let mut pb: Problem<StrExpression> = Problem::default();
+/// pb.objective = StrExpression("x".to_string());
+/// assert!(pb.constraints.is_empty());
+/// ```
+impl<EXPR: Default, VAR> Default for Problem<EXPR, VAR> {
+    fn default() -> Self {
+        Problem::new(
+            "lp_solvers_problem",
+            LpObjective::Minimize,
+            EXPR::default(),
+            vec![],
+            vec![],
+        )
+    }
+}
+
+impl Problem<StrExpression> {
+    /// A copy of any problem, with its expressions in the .lp format
+    pub(crate) fn from_lp_problem<'a, P: LpProblem<'a>>(problem: &'a P) -> Self {
+        let expression = |e: &P::Expression| StrExpression(lp_string(e));
+        let constraint = |c: Constraint<P::Expression>| Constraint {
+            lhs: expression(&c.lhs),
+            operator: c.operator,
+            rhs: c.rhs,
+            name: c.name,
+        };
+        Problem {
+            name: problem.name().to_string(),
+            sense: problem.sense(),
+            objective: StrExpression(objective_lp_string(&problem.objective())),
+            variables: problem
+                .variables()
+                .map(|v| Variable {
+                    name: v.name().to_string(),
+                    kind: v.kind(),
+                    lower_bound: v.lower_bound(),
+                    upper_bound: v.upper_bound(),
+                })
+                .collect(),
+            constraints: problem.constraints().map(constraint).collect(),
+            ranges: problem
+                .ranges()
+                .map(|r| RangeConstraint {
+                    lhs: expression(&r.lhs),
+                    lower: r.lower,
+                    upper: r.upper,
+                    name: r.name,
+                })
+                .collect(),
+            sos: problem.sos_constraints().collect(),
+            indicators: problem
+                .indicator_constraints()
+                .map(|i| IndicatorConstraint {
+                    indicator: i.indicator,
+                    value: i.value,
+                    constraint: constraint(i.constraint),
+                })
+                .collect(),
+            general_constraints: problem.general_constraints().collect(),
+            lazy_constraints: problem.lazy_constraints().map(constraint).collect(),
+            user_cuts: problem.user_cuts().map(constraint).collect(),
+            objective_offset: problem.objective_offset(),
+            objectives: problem
+                .objectives()
+                .map(|o| Objective {
+                    expression: StrExpression(objective_lp_string(&o.expression)),
+                    name: o.name,
+                    priority: o.priority,
+                    weight: o.weight,
+                    abs_tol: o.abs_tol,
+                    rel_tol: o.rel_tol,
+                })
+                .collect(),
+        }
+    }
 }
 
 impl<'a, EXPR: 'a, VAR: 'a> LpProblem<'a> for Problem<EXPR, VAR>
@@ -85,14 +501,61 @@ where
     }
 
     fn constraints(&'a self) -> Self::ConstraintIterator {
-        Box::new(
-            self.constraints
-                .iter()
-                .map(|Constraint { lhs, operator, rhs }| Constraint {
-                    lhs,
-                    operator: *operator,
-                    rhs: *rhs,
-                }),
-        )
+        Box::new(self.constraints.iter().map(Constraint::as_ref))
+    }
+
+    fn ranges(&'a self) -> Box<dyn Iterator<Item = RangeConstraint<Self::Expression>> + 'a> {
+        Box::new(self.ranges.iter().map(|r| RangeConstraint {
+            lhs: &r.lhs,
+            lower: r.lower,
+            upper: r.upper,
+            name: r.name.clone(),
+        }))
+    }
+
+    fn sos_constraints(&'a self) -> Box<dyn Iterator<Item = SosConstraint> + 'a> {
+        Box::new(self.sos.iter().cloned())
+    }
+
+    fn objective_offset(&self) -> f64 {
+        self.objective_offset
+    }
+
+    fn lazy_constraints(&'a self) -> Box<dyn Iterator<Item = Constraint<Self::Expression>> + 'a> {
+        Box::new(self.lazy_constraints.iter().map(Constraint::as_ref))
+    }
+
+    fn user_cuts(&'a self) -> Box<dyn Iterator<Item = Constraint<Self::Expression>> + 'a> {
+        Box::new(self.user_cuts.iter().map(Constraint::as_ref))
+    }
+
+    fn objectives(&'a self) -> Box<dyn Iterator<Item = Objective<Self::Expression>> + 'a> {
+        Box::new(self.objectives.iter().map(|o| Objective {
+            name: o.name.clone(),
+            expression: &o.expression,
+            priority: o.priority,
+            weight: o.weight,
+            abs_tol: o.abs_tol,
+            rel_tol: o.rel_tol,
+        }))
+    }
+
+    fn general_constraints(&'a self) -> Box<dyn Iterator<Item = GeneralConstraint> + 'a> {
+        Box::new(self.general_constraints.iter().cloned())
+    }
+
+    fn indicator_constraints(
+        &'a self,
+    ) -> Box<dyn Iterator<Item = IndicatorConstraint<Self::Expression>> + 'a> {
+        Box::new(self.indicators.iter().map(|i| IndicatorConstraint {
+            indicator: i.indicator.clone(),
+            value: i.value,
+            constraint: Constraint {
+                lhs: &i.constraint.lhs,
+                operator: i.constraint.operator,
+                rhs: i.constraint.rhs,
+                name: i.constraint.name.clone(),
+            },
+        }))
     }
 }
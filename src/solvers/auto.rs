@@ -1,11 +1,11 @@
 //! Auto solvers automatically find which of their child solvers is installed on
 //! the user's computer and uses it. The [AllSolvers] solvers tries all the supported solvers.
 
-use crate::lp_format::{LpObjective, LpProblem};
+use crate::lp_format::{LpObjective, LpProblem, VariableKind};
 use crate::problem::{Problem, StrExpression, Variable};
 #[cfg(feature = "cplex")]
 use crate::solvers::cplex::Cplex;
-use crate::solvers::{CbcSolver, GlpkSolver, GurobiSolver, Solution};
+use crate::solvers::{CbcSolver, GlpkSolver, GurobiSolver, HighsSolver, Solution};
 
 use super::SolverTrait;
 
@@ -20,10 +20,13 @@ pub struct NoSolver;
 #[cfg(not(feature = "cplex"))]
 type Cplex = NoSolver;
 
-/// An [AutoSolver] that tries, in order: Gurobi, Cplex, Cbc and Glpk
+/// An [AutoSolver] that tries, in order: Gurobi, Cplex, HiGHS, Cbc and Glpk
 pub type AllSolvers = AutoSolver<
     GurobiSolver,
-    AutoSolver<Cplex, AutoSolver<CbcSolver, AutoSolver<GlpkSolver, NoSolver>>>,
+    AutoSolver<
+        Cplex,
+        AutoSolver<HighsSolver, AutoSolver<CbcSolver, AutoSolver<GlpkSolver, NoSolver>>>,
+    >,
 >;
 
 impl SolverTrait for NoSolver {
@@ -62,11 +65,11 @@ impl<S: SolverTrait, T: SolverTrait> SolverTrait for AutoSolver<S, T> {
                 objective: StrExpression("x".to_string()),
                 variables: vec![Variable {
                     name: "x".to_string(),
-                    is_integer: false,
+                    kind: VariableKind::Continuous,
                     lower_bound: 0.0,
                     upper_bound: 1.0,
                 }],
-                constraints: vec![],
+                ..Default::default()
             })
             .is_ok();
         if works {
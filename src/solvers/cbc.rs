@@ -7,10 +7,12 @@ use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
 
 use crate::lp_format::*;
+use crate::mps_format::mps_row_names;
 use crate::solvers::{
-    Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds, WithMipGap,
-    WithNbThreads,
+    ProblemFormat, Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds,
+    WithMipGap, WithNbThreads,
 };
+use crate::util::number_after;
 
 /// The coin-or cbc solver
 #[derive(Debug, Clone)]
@@ -21,6 +23,7 @@ pub struct CbcSolver {
     threads: Option<u32>,
     seconds: Option<u32>,
     mipgap: Option<f32>,
+    problem_format: ProblemFormat,
 }
 
 impl Default for CbcSolver {
@@ -39,6 +42,7 @@ impl CbcSolver {
             threads: None,
             seconds: None,
             mipgap: None,
+            problem_format: ProblemFormat::Lp,
         }
     }
 
@@ -51,6 +55,7 @@ impl CbcSolver {
             threads: self.threads,
             seconds: self.seconds,
             mipgap: self.mipgap,
+            problem_format: self.problem_format,
         }
     }
 
@@ -63,6 +68,16 @@ impl CbcSolver {
             threads: self.threads,
             seconds: self.seconds,
             mipgap: self.mipgap,
+            problem_format: self.problem_format,
+        }
+    }
+
+    /// Pass the problem to cbc in the given format instead of the .lp format.
+    /// Cbc recognizes the format from the file extension.
+    pub fn with_problem_format(&self, problem_format: ProblemFormat) -> CbcSolver {
+        CbcSolver {
+            problem_format,
+            ..(*self).clone()
         }
     }
 }
@@ -87,6 +102,7 @@ impl SolverWithSolutionParsing for CbcSolver {
         let mut buffer = String::new();
         let _ = file.read_line(&mut buffer);
 
+        let objective_value = number_after(&buffer, "objective value");
         let mut buffer_split = buffer.split_whitespace();
 
         let status = if let Some(status) = buffer_split.next() {
@@ -112,24 +128,64 @@ impl SolverWithSolutionParsing for CbcSolver {
         } else {
             return Err("Incorrect solution format".to_string());
         };
+        // With "printingOptions all", the rows are listed before the columns
+        let mut lines = vec![];
         for line in file.lines() {
-            let l = line.unwrap();
+            let l = line.map_err(|e| e.to_string())?;
             let mut result_line: Vec<_> = l.split_whitespace().collect();
+            if result_line.is_empty() {
+                continue;
+            }
             if result_line[0] == "**" {
                 result_line.remove(0);
             };
             if result_line.len() == 4 {
-                match result_line[2].parse::<f32>() {
-                    Ok(n) => {
-                        vars_value.insert(result_line[1].to_string(), n);
-                    }
-                    Err(e) => return Err(e.to_string()),
-                }
+                let parse = |s: &str| s.parse::<f32>().map_err(|e| e.to_string());
+                let index: usize = result_line[0]
+                    .parse()
+                    .map_err(|_| "Incorrect solution format")?;
+                lines.push((
+                    index,
+                    result_line[1].to_string(),
+                    parse(result_line[2])?,
+                    parse(result_line[3])?,
+                ));
             } else {
                 return Err("Incorrect solution format".to_string());
             }
         }
-        Ok(Solution::new(status, vars_value))
+        // the rows are the ones of the problem file, whose layout depends on its format
+        let columns_start = match problem {
+            Some(p) => match self.problem_format {
+                ProblemFormat::Lp => row_names(p, self.supported_features()).len(),
+                ProblemFormat::Mps(_) => mps_row_names(p).len(),
+            },
+            // without the problem, the start of the columns is where the indices start again from 0
+            None => (1..lines.len())
+                .find(|&i| lines[i].0 <= lines[i - 1].0)
+                .unwrap_or(0),
+        };
+        if columns_start > lines.len() {
+            return Err("Incorrect solution format: missing rows".to_string());
+        }
+        let mut activities = HashMap::new();
+        let mut dual_values = HashMap::new();
+        for (_, name, activity, dual) in lines.drain(..columns_start) {
+            activities.insert(name.clone(), activity);
+            dual_values.insert(name, dual);
+        }
+        let mut reduced_costs = HashMap::new();
+        for (_, name, value, reduced_cost) in lines {
+            vars_value.insert(name.clone(), value);
+            reduced_costs.insert(name, reduced_cost);
+        }
+        Ok(Solution {
+            objective_value,
+            dual_values,
+            reduced_costs,
+            activities,
+            ..Solution::new(status, vars_value)
+        })
     }
 }
 
@@ -179,6 +235,10 @@ impl SolverProgram for CbcSolver {
         &self.command_name
     }
 
+    fn supported_features(&self) -> &[LpFeature] {
+        &[LpFeature::Sos]
+    }
+
     fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
         let mut args = vec![lp_file.as_os_str().to_owned()];
         if let Some(mipgap) = self.mip_gap() {
@@ -196,13 +256,30 @@ impl SolverProgram for CbcSolver {
                 args.push(val.to_string().into());
             }
         }
-        args.extend_from_slice(&["solve".into(), "solution".into(), solution_file.into()]);
+        args.extend_from_slice(&[
+            "solve".into(),
+            "printingOptions".into(),
+            "all".into(),
+            "solution".into(),
+            solution_file.into(),
+        ]);
         args
     }
 
+    fn problem_format(&self) -> ProblemFormat {
+        self.problem_format
+    }
+
     fn preferred_temp_solution_file(&self) -> Option<&Path> {
         self.temp_solution_file.as_deref()
     }
+
+    fn parse_stdout_objective(&self, stdout: &[u8], solution: &mut Solution) {
+        // cbc ends with a summary that has "Lower bound:" and "Gap:" lines when it stopped early
+        let stdout = String::from_utf8_lossy(stdout);
+        solution.best_bound = number_after(&stdout, "Lower bound:");
+        solution.mip_gap = number_after(&stdout, "Gap:");
+    }
 }
 
 #[cfg(test)]
@@ -219,6 +296,8 @@ mod tests {
         let expected: Vec<OsString> = vec![
             "test.lp".into(),
             "solve".into(),
+            "printingOptions".into(),
+            "all".into(),
             "solution".into(),
             "test.sol".into(),
         ];
@@ -236,6 +315,8 @@ mod tests {
             "seconds".into(),
             "10".into(),
             "solve".into(),
+            "printingOptions".into(),
+            "all".into(),
             "solution".into(),
             "test.sol".into(),
         ];
@@ -256,6 +337,8 @@ mod tests {
             "ratiogap".into(),
             "0.05".to_string().into(),
             "solve".into(),
+            "printingOptions".into(),
+            "all".into(),
             "solution".into(),
             "test.sol".into(),
         ];
@@ -285,6 +368,8 @@ mod tests {
             "threads".into(),
             "3".into(),
             "solve".into(),
+            "printingOptions".into(),
+            "all".into(),
             "solution".into(),
             "test.sol".into(),
         ];
@@ -311,6 +396,8 @@ mod tests {
             "threads".into(),
             "3".into(),
             "solve".into(),
+            "printingOptions".into(),
+            "all".into(),
             "solution".into(),
             "test.sol".into(),
         ];
@@ -0,0 +1,250 @@
+//! The COPT (Cardinal Optimizer) solver.
+//! You need to activate the "copt" feature of this crate to use this solver.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::path::Path;
+
+use crate::lp_format::{LpFeature, LpProblem};
+use crate::solvers::{
+    Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds, WithMipGap,
+    WithNbThreads,
+};
+use crate::util::{buf_contains, number_after};
+
+/// The COPT solver
+#[derive(Debug, Clone)]
+pub struct CoptSolver {
+    command: String,
+    seconds: Option<u32>,
+    threads: Option<u32>,
+    mipgap: Option<f32>,
+}
+
+impl Default for CoptSolver {
+    fn default() -> Self {
+        Self::with_command("copt_cmd".into())
+    }
+}
+
+impl CoptSolver {
+    /// Create a COPT solver from the given binary
+    pub fn with_command(command: String) -> Self {
+        Self {
+            command,
+            seconds: None,
+            threads: None,
+            mipgap: None,
+        }
+    }
+}
+
+impl WithMaxSeconds<CoptSolver> for CoptSolver {
+    fn max_seconds(&self) -> Option<u32> {
+        self.seconds
+    }
+    fn with_max_seconds(&self, seconds: u32) -> CoptSolver {
+        CoptSolver {
+            seconds: Some(seconds),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithNbThreads<CoptSolver> for CoptSolver {
+    fn nb_threads(&self) -> Option<u32> {
+        self.threads
+    }
+    fn with_nb_threads(&self, threads: u32) -> CoptSolver {
+        CoptSolver {
+            threads: Some(threads),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithMipGap<CoptSolver> for CoptSolver {
+    fn mip_gap(&self) -> Option<f32> {
+        self.mipgap
+    }
+
+    fn with_mip_gap(&self, mipgap: f32) -> Result<CoptSolver, String> {
+        if mipgap.is_sign_positive() && mipgap.is_finite() {
+            Ok(CoptSolver {
+                mipgap: Some(mipgap),
+                ..(*self).clone()
+            })
+        } else {
+            Err("Invalid MIP gap: must be positive and finite".to_string())
+        }
+    }
+}
+
+impl SolverProgram for CoptSolver {
+    fn command_name(&self) -> &str {
+        &self.command
+    }
+
+    fn supported_features(&self) -> &[LpFeature] {
+        &[
+            LpFeature::QuadraticObjective,
+            LpFeature::QuadraticConstraints,
+            LpFeature::Sos,
+            LpFeature::Indicators,
+            LpFeature::ObjectiveConstant,
+        ]
+    }
+
+    fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
+        // the commands are separated by semicolons
+        let mut commands = format_osstr!("read " lp_file "; ");
+        if let Some(seconds) = self.max_seconds() {
+            commands.push(format!("set TimeLimit {}; ", seconds));
+        }
+        if let Some(threads) = self.nb_threads() {
+            commands.push(format!("set Threads {}; ", threads));
+        }
+        if let Some(mipgap) = self.mip_gap() {
+            commands.push(format!("set RelGap {}; ", mipgap));
+        }
+        commands.push("optimize; ");
+        commands.push(format_osstr!("write " solution_file "; quit"));
+        vec!["-c".into(), commands]
+    }
+
+    fn parse_stdout_status(&self, stdout: &[u8]) -> Option<Status> {
+        // "Status: Optimal  Objective: 3.5000000000e+00  Iterations: 2  Time: 0.00s"
+        if buf_contains(stdout, "Status: Optimal") {
+            Some(Status::Optimal)
+        } else if buf_contains(stdout, "Status: Infeasible") {
+            Some(Status::Infeasible)
+        } else if buf_contains(stdout, "Status: Unbounded") {
+            Some(Status::Unbounded)
+        } else if buf_contains(stdout, "Status: ") {
+            // a limit was reached, with or without a solution: "Best solution   : --"
+            let stdout = String::from_utf8_lossy(stdout);
+            let best_solution = stdout
+                .lines()
+                .rev()
+                .find(|l| l.starts_with("Best solution"));
+            match best_solution.and_then(|l| number_after(l, ":")) {
+                Some(_) => Some(Status::SubOptimal),
+                None => Some(Status::NotSolved),
+            }
+        } else {
+            None
+        }
+    }
+
+    fn solution_suffix(&self) -> Option<&str> {
+        Some(".sol")
+    }
+
+    fn parse_stdout_objective(&self, stdout: &[u8], solution: &mut Solution) {
+        // "Best bound      : 3.5000000000e+00" and "Best gap        : 0.0000%"
+        let stdout = String::from_utf8_lossy(stdout);
+        let statistic = |name: &str| {
+            let line = stdout.lines().rev().find(|l| l.starts_with(name))?;
+            number_after(line, ":")
+        };
+        solution.best_bound = statistic("Best bound");
+        solution.mip_gap = statistic("Best gap").map(|percent| percent / 100.);
+    }
+}
+
+impl SolverWithSolutionParsing for CoptSolver {
+    fn read_solution_from_path<'a, P: LpProblem<'a>>(
+        &self,
+        temp_solution_file: &Path,
+        problem: Option<&'a P>,
+    ) -> Result<Solution, String> {
+        match File::open(temp_solution_file) {
+            Ok(f) => self.read_specific_solution(&f, problem),
+            // no solution was found
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Ok(Solution::new(Status::NotSolved, HashMap::new()))
+            }
+            Err(e) => Err(format!(
+                "Cannot open solution file {:?}: {}",
+                temp_solution_file, e
+            )),
+        }
+    }
+
+    fn read_specific_solution<'a, P: LpProblem<'a>>(
+        &self,
+        f: &File,
+        _problem: Option<&'a P>,
+    ) -> Result<Solution, String> {
+        // the status comes from the output: the file is only written when there is a solution
+        let mut solution = Solution::new(Status::Optimal, HashMap::new());
+        for line in BufReader::new(f).lines() {
+            let l = line.map_err(|e| e.to_string())?;
+            // "# Objective value: 3.5"
+            if l.starts_with('#') {
+                if let Some(value) = number_after(&l, "Objective value:") {
+                    solution.objective_value = Some(value);
+                }
+                continue;
+            }
+            let result_line: Vec<_> = l.split_whitespace().collect();
+            match result_line[..] {
+                [] => {}
+                [name, value] => {
+                    let value = value
+                        .parse()
+                        .map_err(|e| format!("invalid variable value for {}: {}", name, e))?;
+                    solution.results.insert(name.to_string(), value);
+                }
+                _ => return Err("Incorrect solution format".to_string()),
+            }
+        }
+        Ok(solution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers::{CoptSolver, SolverProgram, WithMaxSeconds, WithMipGap, WithNbThreads};
+    use std::ffi::OsString;
+    use std::path::Path;
+
+    #[test]
+    fn cli_args_default() {
+        let solver = CoptSolver::default();
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "-c".into(),
+            "read test.lp; optimize; write test.sol; quit".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn cli_args_multiple() {
+        let solver = CoptSolver::default()
+            .with_max_seconds(10)
+            .with_nb_threads(3)
+            .with_mip_gap(0.05)
+            .expect("mipgap should be valid");
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "-c".into(),
+            "read test.lp; set TimeLimit 10; set Threads 3; set RelGap 0.05; optimize; write test.sol; quit"
+                .into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn cli_args_mipgap_negative() {
+        let solver = CoptSolver::default().with_mip_gap(-0.05);
+        assert!(solver.is_err());
+    }
+}
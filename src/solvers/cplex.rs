@@ -10,9 +10,9 @@ use std::path::Path;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Reader;
 
-use crate::lp_format::LpProblem;
+use crate::lp_format::{LpFeature, LpProblem};
 use crate::solvers::{Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMipGap};
-use crate::util::buf_contains;
+use crate::util::{buf_contains, number_after};
 
 /// IBM cplex optimizer
 #[derive(Debug, Clone)]
@@ -57,19 +57,26 @@ impl WithMipGap<Cplex> for Cplex {
     }
 }
 
-macro_rules! format_osstr {
-    ($($parts:expr)*) => {{
-        let mut s = OsString::new();
-        $(s.push($parts);)*
-        s
-    }}
-}
-
 impl SolverProgram for Cplex {
     fn command_name(&self) -> &str {
         &self.command
     }
 
+    fn supported_features(&self) -> &[LpFeature] {
+        &[
+            LpFeature::Ranges,
+            LpFeature::QuadraticObjective,
+            LpFeature::QuadraticConstraints,
+            LpFeature::Sos,
+            LpFeature::SemiContinuous,
+            LpFeature::Indicators,
+            LpFeature::LazyConstraints,
+            LpFeature::UserCuts,
+            LpFeature::ObjectiveConstant,
+            LpFeature::MultiObjective,
+        ]
+    }
+
     fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
         let mut args = vec!["-c".into(), format_osstr!("READ \"" lp_file "\"")];
 
@@ -94,13 +101,25 @@ impl SolverProgram for Cplex {
     fn solution_suffix(&self) -> Option<&str> {
         Some(".sol")
     }
+
+    fn parse_stdout_objective(&self, stdout: &[u8], solution: &mut Solution) {
+        // "Current MIP best bound =  1.1000000000e+02 (gap = 10, 8.33%)"
+        let stdout = String::from_utf8_lossy(stdout);
+        solution.best_bound = number_after(&stdout, "best bound =");
+        if let Some(start) = stdout.rfind("(gap =") {
+            let gap = stdout[start..].split(')').next().unwrap_or_default();
+            solution.mip_gap = number_after(gap, ",").map(|percent| percent / 100.);
+        }
+    }
 }
 
+/// The name and value of a variable, and its reduced cost when the problem is continuous
 fn extract_variable_name_and_value_from_event(
     variable_event: BytesStart,
-) -> Result<(String, f32), String> {
+) -> Result<(String, f32, Option<f32>), String> {
     let mut name = None;
     let mut value = None;
+    let mut reduced_cost = None;
     for attribute in variable_event.attributes() {
         let attribute = attribute.map_err(|e| format!("attribute error: {}", e))?;
         match attribute.key.as_ref() {
@@ -112,23 +131,60 @@ fn extract_variable_name_and_value_from_event(
                         .map_err(|e| format!("invalid variable value for {:?}: {}", name, e))?,
                 );
             }
+            b"reducedCost" => {
+                reduced_cost = Some(
+                    String::from_utf8_lossy(attribute.value.as_ref())
+                        .parse()
+                        .map_err(|e| format!("invalid reduced cost for {:?}: {}", name, e))?,
+                );
+            }
             _ => {}
         }
     }
 
-    name.and_then(|name| value.map(|value| (name, value)))
+    name.and_then(|name| value.map(|value| (name, value, reduced_cost)))
         .ok_or_else(|| "name and value not found for variable".to_string())
 }
 
+/// The name and slack of a constraint, and its dual value when the problem is continuous
+fn extract_constraint_name_slack_and_dual_from_event(
+    constraint_event: BytesStart,
+) -> Result<(String, Option<f32>, Option<f32>), String> {
+    let mut name = None;
+    let mut slack = None;
+    let mut dual = None;
+    for attribute in constraint_event.attributes() {
+        let attribute = attribute.map_err(|e| format!("attribute error: {}", e))?;
+        match attribute.key.as_ref() {
+            b"name" => name = Some(String::from_utf8_lossy(attribute.value.as_ref()).to_string()),
+            b"slack" => {
+                slack = Some(
+                    String::from_utf8_lossy(attribute.value.as_ref())
+                        .parse()
+                        .map_err(|e| format!("invalid slack for {:?}: {}", name, e))?,
+                );
+            }
+            b"dual" => {
+                dual = Some(
+                    String::from_utf8_lossy(attribute.value.as_ref())
+                        .parse()
+                        .map_err(|e| format!("invalid dual value for {:?}: {}", name, e))?,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    name.map(|name| (name, slack, dual))
+        .ok_or_else(|| "name not found for constraint".to_string())
+}
+
 fn read_specific_solution(f: &File, variables_len: Option<usize>) -> Result<Solution, String> {
     let results = variables_len
         .map(HashMap::with_capacity)
         .unwrap_or_default();
 
-    let mut solution = Solution {
-        status: Status::Optimal,
-        results,
-    };
+    let mut solution = Solution::new(Status::Optimal, results);
 
     let f = BufReader::new(f);
     let mut reader = Reader::from_reader(f);
@@ -147,6 +203,51 @@ fn read_specific_solution(f: &File, variables_len: Option<usize>) -> Result<Solu
             Ok(Event::Eof) => {
                 break;
             }
+            // the header holds the objective value
+            Ok(Event::Empty(e)) | Ok(Event::Start(e)) if e.local_name().as_ref() == b"header" => {
+                for attribute in e.attributes() {
+                    let attribute = attribute.map_err(|e| format!("attribute error: {}", e))?;
+                    if attribute.key.as_ref() == b"objectiveValue" {
+                        let value = String::from_utf8_lossy(attribute.value.as_ref());
+                        solution.objective_value =
+                            Some(value.parse().map_err(|e| {
+                                format!("invalid objective value {}: {}", value, e)
+                            })?);
+                    }
+                }
+            }
+            // the "linearConstraints" section comes before the variables
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"linearConstraints" => loop {
+                match reader.read_event_into(&mut buf) {
+                    Ok(Event::Empty(e)) | Ok(Event::Start(e))
+                        if e.local_name().as_ref() == b"constraint" =>
+                    {
+                        let (name, slack, dual) =
+                            extract_constraint_name_slack_and_dual_from_event(e)?;
+                        if let Some(slack) = slack {
+                            solution.slacks.insert(name.clone(), slack);
+                        }
+                        if let Some(dual) = dual {
+                            solution.dual_values.insert(name, dual);
+                        }
+                    }
+                    Ok(Event::End(e)) if e.local_name().as_ref() == b"linearConstraints" => break,
+                    Err(e) => {
+                        return Err(format!(
+                            "Error at position {}: {:?}",
+                            reader.buffer_position(),
+                            e
+                        ))
+                    }
+                    Ok(Event::Eof) => {
+                        return Err(format!(
+                            "Error at position {}: Unterminated linearConstraints section",
+                            reader.buffer_position(),
+                        ))
+                    }
+                    _ => {}
+                }
+            },
             // we reached the "variables" section, where the variables to parse are
             Ok(Event::Start(e)) if e.local_name().as_ref() == b"variables" => loop {
                 match reader.read_event_into(&mut buf) {
@@ -155,7 +256,11 @@ fn read_specific_solution(f: &File, variables_len: Option<usize>) -> Result<Solu
                         if e.local_name().as_ref() == b"variable" =>
                     {
                         // let's try to parse the variable name and value
-                        let (name, value) = extract_variable_name_and_value_from_event(e)?;
+                        let (name, value, reduced_cost) =
+                            extract_variable_name_and_value_from_event(e)?;
+                        if let Some(reduced_cost) = reduced_cost {
+                            solution.reduced_costs.insert(name.clone(), reduced_cost);
+                        }
                         solution.results.insert(name, value);
                     }
                     // we reached the end of the "variables" section, at this point all the variables should have been parsed.
@@ -202,7 +307,7 @@ impl SolverWithSolutionParsing for Cplex {
 #[cfg(test)]
 mod tests {
     use super::read_specific_solution;
-    use crate::solvers::{Cplex, SolverProgram, WithMipGap};
+    use crate::solvers::{Cplex, Solution, SolverProgram, Status, WithMipGap};
     use std::collections::HashMap;
     use std::ffi::OsString;
     use std::io::{Seek, Write};
@@ -245,6 +350,31 @@ mod tests {
  </variables>
 </CPLEXSolution>"##;
 
+    const CONTINUOUS_SOL_FILE: &str = r##"<?xml version = "1.0" standalone="yes"?>
+<CPLEXSolution version="1.2">
+ <header
+   problemName="continuous.lp"
+   solutionName="incumbent"
+   solutionIndex="-1"
+   objectiveValue="3.5"
+   solutionTypeValue="1"
+   solutionTypeString="basic"
+   solutionStatusValue="1"
+   solutionStatusString="optimal"
+   solutionMethodString="dual"
+   primalFeasible="1"
+   dualFeasible="1"
+   simplexIterations="2"/>
+ <linearConstraints>
+  <constraint name="c0" index="0" status="UL" slack="0" dual="2"/>
+  <constraint name="c1" index="1" status="BS" slack="1.5" dual="0"/>
+ </linearConstraints>
+ <variables>
+  <variable name="x" index="0" status="BS" value="1.5" reducedCost="0"/>
+  <variable name="y" index="1" status="LL" value="0" reducedCost="1"/>
+ </variables>
+</CPLEXSolution>"##;
+
     #[test]
     fn sol_file_parsing() {
         let mut tmpfile = tempfile::tempfile().expect("unable to create tempfile");
@@ -266,6 +396,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn continuous_sol_file_parsing() {
+        let mut tmpfile = tempfile::tempfile().expect("unable to create tempfile");
+        tmpfile
+            .write_all(CONTINUOUS_SOL_FILE.as_bytes())
+            .expect("unable to write sol file to tempfile");
+        tmpfile.rewind().expect("unable to rewind sol file");
+
+        let solution = read_specific_solution(&tmpfile, None).expect("failed to read sol file");
+
+        assert_eq!(solution.objective_value, Some(3.5));
+        assert_eq!(
+            solution.dual_values,
+            HashMap::from([("c0".to_owned(), 2.), ("c1".to_owned(), 0.)])
+        );
+        assert_eq!(
+            solution.slacks,
+            HashMap::from([("c0".to_owned(), 0.), ("c1".to_owned(), 1.5)])
+        );
+        assert_eq!(
+            solution.reduced_costs,
+            HashMap::from([("x".to_owned(), 0.), ("y".to_owned(), 1.)])
+        );
+    }
+
+    #[test]
+    fn stdout_bound_and_gap() {
+        let mut solution = Solution::new(Status::SubOptimal, HashMap::new());
+        Cplex::default().parse_stdout_objective(
+            b"MIP - Time limit exceeded, integer feasible:  Objective =  1.2000000000e+02\nCurrent MIP best bound =  1.1000000000e+02 (gap = 10, 8.33%)\n",
+            &mut solution,
+        );
+        assert_eq!(solution.best_bound, Some(110.));
+        assert_eq!(solution.mip_gap, Some(0.0833));
+    }
+
     #[test]
     fn cli_args_default() {
         let solver = Cplex::default();
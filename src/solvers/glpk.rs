@@ -8,9 +8,12 @@ use std::io::{BufRead, BufReader, Error};
 use std::path::{Path, PathBuf};
 
 use crate::lp_format::*;
+use crate::mps_format::MpsFormat;
 use crate::solvers::{
-    Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds, WithMipGap,
+    ProblemFormat, Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds,
+    WithMipGap,
 };
+use crate::util::number_after;
 
 /// glpk solver
 #[derive(Debug, Clone)]
@@ -20,6 +23,7 @@ pub struct GlpkSolver {
     temp_solution_file: Option<PathBuf>,
     seconds: Option<u32>,
     mipgap: Option<f32>,
+    problem_format: ProblemFormat,
 }
 
 impl Default for GlpkSolver {
@@ -37,6 +41,7 @@ impl GlpkSolver {
             temp_solution_file: None,
             seconds: None,
             mipgap: None,
+            problem_format: ProblemFormat::Lp,
         }
     }
     /// Set the glpk command name
@@ -47,6 +52,7 @@ impl GlpkSolver {
             temp_solution_file: self.temp_solution_file.clone(),
             seconds: self.seconds,
             mipgap: self.mipgap,
+            problem_format: self.problem_format,
         }
     }
     /// Set the temporary solution file to use
@@ -57,6 +63,14 @@ impl GlpkSolver {
             temp_solution_file: Some(temp_solution_file.into()),
             seconds: self.seconds,
             mipgap: self.mipgap,
+            problem_format: self.problem_format,
+        }
+    }
+    /// Pass the problem to glpk in the given format instead of the .lp format
+    pub fn with_problem_format(&self, problem_format: ProblemFormat) -> GlpkSolver {
+        GlpkSolver {
+            problem_format,
+            ..(*self).clone()
         }
     }
 }
@@ -84,14 +98,8 @@ impl SolverWithSolutionParsing for GlpkSolver {
         let file = BufReader::new(f);
 
         let mut iter = file.lines();
-        let row = match read_size(iter.nth(1)) {
-            Ok(value) => value,
-            Err(e) => return Err(e),
-        };
-        let col = match read_size(iter.next()) {
-            Ok(value) => value,
-            Err(e) => return Err(e),
-        };
+        let row = read_size(iter.nth(1))?;
+        let col = read_size(iter.next())?;
         let status = match iter.nth(1) {
             Some(Ok(status_line)) => match &status_line[12..] {
                 "INTEGER OPTIMAL" | "OPTIMAL" => Status::Optimal,
@@ -103,29 +111,98 @@ impl SolverWithSolutionParsing for GlpkSolver {
             },
             _ => return Err("Incorrect solution format: No solution status found".to_string()),
         };
-        let mut result_lines = iter.skip(row + 7);
-        for _ in 0..col {
-            let line = match result_lines.next() {
-                Some(Ok(l)) => l,
-                _ => {
-                    return Err("Incorrect solution format: Not all columns are present".to_string())
+        // "Objective:  obj = 100 (MAXimum)"
+        let objective_value = match iter.next() {
+            Some(Ok(objective_line)) => number_after(&objective_line, "="),
+            _ => return Err("Incorrect solution format: No objective found".to_string()),
+        };
+        // the marginal columns (dual values and reduced costs) are there for continuous problems only
+        let has_marginals = match iter.nth(1) {
+            Some(Ok(header)) => header.contains("Marginal"),
+            _ => return Err("Incorrect solution format: No row section found".to_string()),
+        };
+        let mut result_lines = iter.skip(1);
+        let mut activities = HashMap::new();
+        let mut dual_values = HashMap::new();
+        for _ in 0..row {
+            let (name, fields) = read_entry(&mut result_lines, "rows")?;
+            match field(&fields, 23, 36).parse::<f32>() {
+                Ok(n) => {
+                    activities.insert(name.clone(), n);
                 }
-            };
-            let result_line: Vec<_> = line.split_whitespace().collect();
-            if result_line.len() >= 4 {
-                match result_line[3].parse::<f32>() {
-                    Ok(n) => {
-                        vars_value.insert(result_line[1].to_string(), n);
-                    }
-                    Err(e) => return Err(e.to_string()),
+                Err(e) => return Err(e.to_string()),
+            }
+            if has_marginals {
+                dual_values.insert(name, parse_marginal(field(&fields, 65, 78))?);
+            }
+        }
+        let mut result_lines = result_lines.skip(3);
+        let mut reduced_costs = HashMap::new();
+        for _ in 0..col {
+            let (name, fields) = read_entry(&mut result_lines, "columns")?;
+            match field(&fields, 23, 36).parse::<f32>() {
+                Ok(n) => {
+                    vars_value.insert(name.clone(), n);
                 }
-            } else {
-                return Err(
-                    "Incorrect solution format: Column specification has to few fields".to_string(),
-                );
+                Err(e) => return Err(e.to_string()),
+            }
+            if has_marginals {
+                reduced_costs.insert(name, parse_marginal(field(&fields, 65, 78))?);
             }
         }
-        Ok(Solution::new(status, vars_value))
+        Ok(Solution {
+            objective_value,
+            dual_values,
+            reduced_costs,
+            activities,
+            ..Solution::new(status, vars_value)
+        })
+    }
+}
+
+/// Read a row or a column, and return its name and the line that holds its values.
+/// Names longer than 12 characters are alone on their line, and the values are on the next one.
+fn read_entry(
+    lines: &mut impl Iterator<Item = Result<String, Error>>,
+    section: &str,
+) -> Result<(String, String), String> {
+    let mut next_line = || match lines.next() {
+        Some(Ok(l)) => Ok(l),
+        _ => Err(format!(
+            "Incorrect solution format: Not all {} are present",
+            section
+        )),
+    };
+    let line = next_line()?;
+    let mut tokens = line.split_whitespace();
+    let name = match tokens.nth(1) {
+        Some(name) => name.to_string(),
+        None => return Err("Incorrect solution format: missing name".to_string()),
+    };
+    if tokens.next().is_some() {
+        Ok((name, line))
+    } else {
+        Ok((name, next_line()?))
+    }
+}
+
+/// The trimmed text between the given character positions of a line of the row or column section
+fn field(line: &str, start: usize, end: usize) -> &str {
+    line.get(start..end.min(line.len()))
+        .unwrap_or_default()
+        .trim()
+}
+
+/// The marginal column is empty for basic rows, and "< eps" when it is tiny
+fn parse_marginal(marginal: &str) -> Result<f32, String> {
+    match marginal {
+        "" | "< eps" => Ok(0.),
+        value => value.parse().map_err(|e| {
+            format!(
+                "Incorrect solution format: invalid marginal {}: {}",
+                value, e
+            )
+        }),
     }
 }
 
@@ -164,9 +241,18 @@ impl SolverProgram for GlpkSolver {
         &self.command_name
     }
 
+    fn supported_features(&self) -> &[LpFeature] {
+        &[LpFeature::Ranges]
+    }
+
     fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
+        let format_flag = match self.problem_format {
+            ProblemFormat::Lp => "--lp",
+            ProblemFormat::Mps(MpsFormat::Fixed) => "--mps",
+            ProblemFormat::Mps(MpsFormat::Free) => "--freemps",
+        };
         let mut args = vec![
-            "--lp".into(),
+            format_flag.into(),
             lp_file.into(),
             "-o".into(),
             solution_file.into(),
@@ -185,6 +271,10 @@ impl SolverProgram for GlpkSolver {
         args
     }
 
+    fn problem_format(&self) -> ProblemFormat {
+        self.problem_format
+    }
+
     fn preferred_temp_solution_file(&self) -> Option<&Path> {
         self.temp_solution_file.as_deref()
     }
@@ -192,7 +282,8 @@ impl SolverProgram for GlpkSolver {
 
 #[cfg(test)]
 mod tests {
-    use crate::solvers::{GlpkSolver, SolverProgram, WithMaxSeconds, WithMipGap};
+    use crate::mps_format::MpsFormat;
+    use crate::solvers::{GlpkSolver, ProblemFormat, SolverProgram, WithMaxSeconds, WithMipGap};
     use std::ffi::OsString;
     use std::path::Path;
 
@@ -260,6 +351,21 @@ mod tests {
         assert!(solver.is_err());
     }
 
+    #[test]
+    fn cli_args_free_mps() {
+        let solver = GlpkSolver::new().with_problem_format(ProblemFormat::Mps(MpsFormat::Free));
+        let args = solver.arguments(Path::new("test.mps"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "--freemps".into(),
+            "test.mps".into(),
+            "-o".into(),
+            "test.sol".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
     #[test]
     fn cli_args_multiple() {
         let solver = GlpkSolver::new()
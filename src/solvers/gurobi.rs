@@ -2,12 +2,14 @@
 use std::collections::HashMap;
 use std::ffi::OsString;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
 
+use serde_json::Value;
+
 use crate::lp_format::*;
 use crate::solvers::{Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMipGap};
-use crate::util::buf_contains;
+use crate::util::{buf_contains, number_after};
 
 /// The proprietary gurobi solver
 #[derive(Debug, Clone)]
@@ -16,6 +18,7 @@ pub struct GurobiSolver {
     command_name: String,
     temp_solution_file: Option<PathBuf>,
     mipgap: Option<f32>,
+    json_solution: bool,
 }
 
 impl Default for GurobiSolver {
@@ -32,6 +35,7 @@ impl GurobiSolver {
             command_name: "gurobi_cl".to_string(),
             temp_solution_file: None,
             mipgap: None,
+            json_solution: false,
         }
     }
     /// set the name of the commandline gurobi executable to use
@@ -41,6 +45,15 @@ impl GurobiSolver {
             command_name,
             temp_solution_file: self.temp_solution_file.clone(),
             mipgap: self.mipgap,
+            json_solution: self.json_solution,
+        }
+    }
+    /// Read the result from a JSON solution file, which has the status, the bound,
+    /// the dual values, the reduced costs and the slacks. Needs gurobi 9.5 or later.
+    pub fn with_json_solution(&self) -> GurobiSolver {
+        GurobiSolver {
+            json_solution: true,
+            ..(*self).clone()
         }
     }
 }
@@ -52,16 +65,23 @@ impl SolverWithSolutionParsing for GurobiSolver {
         _problem: Option<&'a P>,
     ) -> Result<Solution, String> {
         let mut vars_value: HashMap<_, _> = HashMap::new();
+        let mut objective_value = None;
         let mut file = BufReader::new(f);
         let mut buffer = String::new();
         let _ = file.read_line(&mut buffer);
 
+        if buffer.trim_start().starts_with('{') {
+            file.read_to_string(&mut buffer)
+                .map_err(|e| format!("Cannot read solution file: {}", e))?;
+            return read_json_solution(&buffer);
+        }
         if buffer.split(' ').next().is_some() {
             for line in file.lines() {
                 let l = line.unwrap();
 
                 // Gurobi version 7 add comments on the header file
                 if let Some('#') = l.chars().next() {
+                    objective_value = objective_value.or(number_after(&l, "Objective value ="));
                     continue;
                 }
 
@@ -80,10 +100,58 @@ impl SolverWithSolutionParsing for GurobiSolver {
         } else {
             return Err("Incorrect solution format".to_string());
         }
-        Ok(Solution::new(Status::Optimal, vars_value))
+        Ok(Solution {
+            objective_value,
+            ..Solution::new(Status::Optimal, vars_value)
+        })
     }
 }
 
+/// Read a solution file in the JSON format, that gurobi writes when the result file ends with .json
+fn read_json_solution(content: &str) -> Result<Solution, String> {
+    let json: Value =
+        serde_json::from_str(content).map_err(|e| format!("Incorrect solution format: {}", e))?;
+    let info = &json["SolutionInfo"];
+    let status = match info["Status"].as_i64() {
+        Some(2) => Status::Optimal,
+        Some(3) => Status::Infeasible,
+        Some(5) => Status::Unbounded,
+        // 4 is "infeasible or unbounded"
+        Some(4) => Status::NotSolved,
+        // the limits, and the other reasons to stop early
+        Some(_) if info["SolCount"].as_i64().unwrap_or(0) > 0 => Status::SubOptimal,
+        _ => Status::NotSolved,
+    };
+    let mut solution = Solution {
+        objective_value: info["ObjVal"].as_f64(),
+        best_bound: info["ObjBound"].as_f64(),
+        mip_gap: info["MIPGap"].as_f64(),
+        ..Solution::new(status, HashMap::new())
+    };
+    for var in json["Vars"].as_array().into_iter().flatten() {
+        if let (Some(name), Some(value)) = (var["VarName"].as_str(), var["X"].as_f64()) {
+            solution.results.insert(name.to_string(), value as f32);
+        }
+        if let (Some(name), Some(rc)) = (var["VarName"].as_str(), var["RC"].as_f64()) {
+            solution.reduced_costs.insert(name.to_string(), rc as f32);
+        }
+    }
+    for constraint in json["Constrs"].as_array().into_iter().flatten() {
+        if let (Some(name), Some(slack)) = (
+            constraint["ConstrName"].as_str(),
+            constraint["Slack"].as_f64(),
+        ) {
+            solution.slacks.insert(name.to_string(), slack as f32);
+        }
+        if let (Some(name), Some(dual)) =
+            (constraint["ConstrName"].as_str(), constraint["Pi"].as_f64())
+        {
+            solution.dual_values.insert(name.to_string(), dual as f32);
+        }
+    }
+    Ok(solution)
+}
+
 impl WithMipGap<GurobiSolver> for GurobiSolver {
     fn mip_gap(&self) -> Option<f32> {
         self.mipgap
@@ -106,11 +174,31 @@ impl SolverProgram for GurobiSolver {
         &self.command_name
     }
 
+    fn supported_features(&self) -> &[LpFeature] {
+        &[
+            LpFeature::Ranges,
+            LpFeature::QuadraticObjective,
+            LpFeature::QuadraticConstraints,
+            LpFeature::Sos,
+            LpFeature::SemiContinuous,
+            LpFeature::Indicators,
+            LpFeature::GeneralConstraints,
+            LpFeature::LazyConstraints,
+            LpFeature::UserCuts,
+            LpFeature::ObjectiveConstant,
+            LpFeature::MultiObjective,
+        ]
+    }
+
     fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
         let mut arg0: OsString = "ResultFile=".into();
         arg0.push(solution_file.as_os_str());
 
         let mut args = vec![arg0];
+        if self.json_solution {
+            // list all the variables and constraints in the result file, not only the non-zero ones
+            args.push("JSONSolDetail=1".into());
+        }
 
         if let Some(mipgap) = self.mip_gap() {
             let mut arg_mipgap: OsString = "MIPGap=".into();
@@ -128,7 +216,8 @@ impl SolverProgram for GurobiSolver {
     }
 
     fn solution_suffix(&self) -> Option<&str> {
-        Some(".sol")
+        // gurobi picks the format of the result file from its extension
+        Some(if self.json_solution { ".json" } else { ".sol" })
     }
 
     fn parse_stdout_status(&self, stdout: &[u8]) -> Option<Status> {
@@ -140,6 +229,18 @@ impl SolverProgram for GurobiSolver {
             None
         }
     }
+
+    fn parse_stdout_objective(&self, stdout: &[u8], solution: &mut Solution) {
+        // "Best objective 1.000000000000e+00, best bound 1.000000000000e+00, gap 0.0000%"
+        // keep the values from the json result file when the output has none
+        let stdout = String::from_utf8_lossy(stdout);
+        if let Some(bound) = number_after(&stdout, "best bound") {
+            solution.best_bound = Some(bound);
+        }
+        if let Some(percent) = number_after(&stdout, ", gap") {
+            solution.mip_gap = Some(percent / 100.);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -156,6 +257,7 @@ mod tests {
         let expected: Vec<OsString> = vec!["ResultFile=test.sol".into(), "test.lp".into()];
 
         assert_eq!(args, expected);
+        assert_eq!(solver.solution_suffix(), Some(".sol"));
     }
 
     #[test]
@@ -175,6 +277,21 @@ mod tests {
         assert_eq!(args, expected);
     }
 
+    #[test]
+    fn cli_args_json_solution() {
+        let solver = GurobiSolver::new().with_json_solution();
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.json"));
+
+        let expected: Vec<OsString> = vec![
+            "ResultFile=test.json".into(),
+            "JSONSolDetail=1".into(),
+            "test.lp".into(),
+        ];
+
+        assert_eq!(args, expected);
+        assert_eq!(solver.solution_suffix(), Some(".json"));
+    }
+
     #[test]
     fn cli_args_mipgap_negative() {
         let solver = GurobiSolver::new().with_mip_gap(-0.05);
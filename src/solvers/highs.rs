@@ -0,0 +1,310 @@
+//! The HiGHS solver.
+//! [https://highs.dev/]
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::iter::Peekable;
+use std::path::{Path, PathBuf};
+
+use crate::lp_format::*;
+use crate::solvers::{
+    Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds, WithMipGap,
+    WithNbThreads,
+};
+use crate::util::number_after;
+
+/// The HiGHS solver
+#[derive(Debug, Clone)]
+pub struct HighsSolver {
+    command_name: String,
+    temp_solution_file: Option<PathBuf>,
+    threads: Option<u32>,
+    seconds: Option<u32>,
+    mipgap: Option<f32>,
+}
+
+impl Default for HighsSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HighsSolver {
+    /// Create a HiGHS solver instance
+    pub fn new() -> HighsSolver {
+        HighsSolver {
+            command_name: "highs".to_string(),
+            temp_solution_file: None,
+            threads: None,
+            seconds: None,
+            mipgap: None,
+        }
+    }
+
+    /// set the name of the executable to use
+    pub fn command_name(&self, command_name: String) -> HighsSolver {
+        HighsSolver {
+            command_name,
+            ..(*self).clone()
+        }
+    }
+
+    /// Set the temporary solution file to use
+    pub fn with_temp_solution_file(&self, temp_solution_file: String) -> HighsSolver {
+        HighsSolver {
+            temp_solution_file: Some(temp_solution_file.into()),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl SolverWithSolutionParsing for HighsSolver {
+    fn read_specific_solution<'a, P: LpProblem<'a>>(
+        &self,
+        f: &File,
+        _problem: Option<&'a P>,
+    ) -> Result<Solution, String> {
+        let mut content = String::new();
+        BufReader::new(f)
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Cannot read solution file: {}", e))?;
+        let mut lines = content.lines().map(str::trim).peekable();
+        let model_status = match (lines.next(), lines.next()) {
+            (Some("Model status"), Some(status)) => status,
+            _ => return Err("Incorrect solution format: No model status found".to_string()),
+        };
+        let mut solution = Solution::new(Status::NotSolved, HashMap::new());
+        let mut primal_feasible = false;
+        while let Some(line) = lines.next() {
+            match line {
+                "# Primal solution values" => {
+                    match lines.next() {
+                        Some("None") | None => continue,
+                        Some(feasibility) => primal_feasible = feasibility == "Feasible",
+                    }
+                    if let Some(objective) = lines.next_if(|l| l.starts_with("Objective")) {
+                        solution.objective_value = number_after(objective, "Objective");
+                    }
+                    read_values(&mut lines, &mut solution.results, &mut solution.activities)?;
+                }
+                "# Dual solution values" => {
+                    if let Some("None") | None = lines.next() {
+                        continue;
+                    }
+                    read_values(
+                        &mut lines,
+                        &mut solution.reduced_costs,
+                        &mut solution.dual_values,
+                    )?;
+                }
+                "# Basis" => break,
+                _ => {}
+            }
+        }
+        solution.status = match model_status {
+            "Optimal" | "Empty" => Status::Optimal,
+            "Infeasible" => Status::Infeasible,
+            "Primal infeasible or unbounded" => Status::NotSolved,
+            "Unbounded" => Status::Unbounded,
+            // the limits, and the other reasons to stop early
+            _ if primal_feasible => Status::SubOptimal,
+            _ => Status::NotSolved,
+        };
+        Ok(solution)
+    }
+}
+
+/// Read the "# Columns" and "# Rows" lists of a section of the solution file
+fn read_values<'a>(
+    lines: &mut Peekable<impl Iterator<Item = &'a str>>,
+    columns: &mut HashMap<String, f32>,
+    rows: &mut HashMap<String, f32>,
+) -> Result<(), String> {
+    for (section, values) in [("# Columns", columns), ("# Rows", rows)] {
+        let count = match lines.next() {
+            Some(header) if header.starts_with(section) => number_after(header, section),
+            _ => None,
+        };
+        let count = count.ok_or_else(|| format!("Incorrect solution format: No {}", section))?;
+        for _ in 0..count as usize {
+            let line = lines.next().unwrap_or_default();
+            let mut tokens = line.split_whitespace();
+            match (tokens.next(), tokens.next().map(str::parse::<f32>)) {
+                (Some(name), Some(Ok(value))) => {
+                    values.insert(name.to_string(), value);
+                }
+                _ => return Err(format!("Incorrect solution format: {:?}", line)),
+            }
+        }
+    }
+    Ok(())
+}
+
+impl WithMaxSeconds<HighsSolver> for HighsSolver {
+    fn max_seconds(&self) -> Option<u32> {
+        self.seconds
+    }
+    fn with_max_seconds(&self, seconds: u32) -> HighsSolver {
+        HighsSolver {
+            seconds: Some(seconds),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithMipGap<HighsSolver> for HighsSolver {
+    fn mip_gap(&self) -> Option<f32> {
+        self.mipgap
+    }
+
+    fn with_mip_gap(&self, mipgap: f32) -> Result<HighsSolver, String> {
+        if mipgap.is_sign_positive() && mipgap.is_finite() {
+            Ok(HighsSolver {
+                mipgap: Some(mipgap),
+                ..(*self).clone()
+            })
+        } else {
+            Err("Invalid MIP gap: must be positive and finite".to_string())
+        }
+    }
+}
+
+impl WithNbThreads<HighsSolver> for HighsSolver {
+    fn nb_threads(&self) -> Option<u32> {
+        self.threads
+    }
+    fn with_nb_threads(&self, threads: u32) -> HighsSolver {
+        HighsSolver {
+            threads: Some(threads),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl SolverProgram for HighsSolver {
+    fn command_name(&self) -> &str {
+        &self.command_name
+    }
+
+    fn supported_features(&self) -> &[LpFeature] {
+        &[
+            LpFeature::Ranges,
+            LpFeature::QuadraticObjective,
+            LpFeature::SemiContinuous,
+            LpFeature::ObjectiveConstant,
+        ]
+    }
+
+    fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
+        let mut args = vec![
+            "--model_file".into(),
+            lp_file.into(),
+            "--solution_file".into(),
+            solution_file.into(),
+        ];
+        if let Some(seconds) = self.max_seconds() {
+            args.push("--time_limit".into());
+            args.push(seconds.to_string().into());
+        }
+        args
+    }
+
+    fn preferred_temp_solution_file(&self) -> Option<&Path> {
+        self.temp_solution_file.as_deref()
+    }
+
+    // the number of threads and the gap have no commandline argument
+    fn options_file_content(&self) -> Option<String> {
+        let mut options = String::new();
+        if let Some(threads) = self.nb_threads() {
+            options.push_str(&format!("threads = {}\n", threads));
+        }
+        if let Some(mipgap) = self.mip_gap() {
+            options.push_str(&format!("mip_rel_gap = {}\n", mipgap));
+        }
+        Some(options).filter(|o| !o.is_empty())
+    }
+
+    fn options_file_arguments(&self, options_file: &Path) -> Vec<OsString> {
+        vec!["--options_file".into(), options_file.into()]
+    }
+
+    fn parse_stdout_objective(&self, stdout: &[u8], solution: &mut Solution) {
+        // "  Dual bound        -170" and "  Gap               0% (tolerance: 0.01%)"
+        let stdout = String::from_utf8_lossy(stdout);
+        // only the final summary is read: the options echoed before it mention the gap too
+        let report = stdout
+            .rfind("Solving report")
+            .map_or(&stdout[..], |i| &stdout[i..]);
+        let statistic = |name: &str| {
+            let line = report.lines().find(|l| l.trim_start().starts_with(name))?;
+            number_after(line, name)
+        };
+        solution.best_bound = statistic("Dual bound");
+        solution.mip_gap = statistic("Gap").map(|percent| percent / 100.);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers::{HighsSolver, SolverProgram, WithMaxSeconds, WithMipGap, WithNbThreads};
+    use std::ffi::OsString;
+    use std::path::Path;
+
+    #[test]
+    fn cli_args_default() {
+        let solver = HighsSolver::new();
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "--model_file".into(),
+            "test.lp".into(),
+            "--solution_file".into(),
+            "test.sol".into(),
+        ];
+
+        assert_eq!(args, expected);
+        assert_eq!(solver.options_file_content(), None);
+    }
+
+    #[test]
+    fn cli_args_seconds() {
+        let solver = HighsSolver::new().with_max_seconds(10);
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "--model_file".into(),
+            "test.lp".into(),
+            "--solution_file".into(),
+            "test.sol".into(),
+            "--time_limit".into(),
+            "10".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn options_file() {
+        let solver = HighsSolver::new()
+            .with_nb_threads(3)
+            .with_mip_gap(0.05)
+            .expect("mipgap should be valid");
+
+        assert_eq!(
+            solver.options_file_content().as_deref(),
+            Some("threads = 3\nmip_rel_gap = 0.05\n")
+        );
+        assert_eq!(
+            solver.options_file_arguments(Path::new("test.opt")),
+            vec![OsString::from("--options_file"), "test.opt".into()]
+        );
+    }
+
+    #[test]
+    fn cli_args_mipgap_negative() {
+        let solver = HighsSolver::new().with_mip_gap(-0.05);
+        assert!(solver.is_err());
+    }
+}
@@ -0,0 +1,260 @@
+//! The lp_solve solver.
+//! [https://lpsolve.sourceforge.net/]
+//!
+//! lp_solve reads the problem in the free MPS format, and prints the solution.
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::lp_format::*;
+use crate::mps_format::MpsFormat;
+use crate::solvers::{
+    ProblemFormat, Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds,
+    WithMipGap,
+};
+use crate::util::number_after;
+
+/// The lp_solve solver
+#[derive(Debug, Clone)]
+pub struct LpSolveSolver {
+    command_name: String,
+    temp_solution_file: Option<PathBuf>,
+    seconds: Option<u32>,
+    mipgap: Option<f32>,
+}
+
+impl Default for LpSolveSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LpSolveSolver {
+    /// Create an lp_solve solver instance
+    pub fn new() -> LpSolveSolver {
+        LpSolveSolver {
+            command_name: "lp_solve".to_string(),
+            temp_solution_file: None,
+            seconds: None,
+            mipgap: None,
+        }
+    }
+
+    /// set the name of the executable to use
+    pub fn command_name(&self, command_name: String) -> LpSolveSolver {
+        LpSolveSolver {
+            command_name,
+            ..(*self).clone()
+        }
+    }
+
+    /// Set the temporary file where the printed solution is saved
+    pub fn with_temp_solution_file(&self, temp_solution_file: String) -> LpSolveSolver {
+        LpSolveSolver {
+            temp_solution_file: Some(temp_solution_file.into()),
+            ..(*self).clone()
+        }
+    }
+}
+
+/// The part of the printout that is being read
+enum Section {
+    Variables,
+    Constraints,
+    /// The dual values, with the number of constraints still to be read before the variables
+    Duals(usize),
+}
+
+impl SolverWithSolutionParsing for LpSolveSolver {
+    fn read_specific_solution<'a, P: LpProblem<'a>>(
+        &self,
+        f: &File,
+        _problem: Option<&'a P>,
+    ) -> Result<Solution, String> {
+        let mut solution = Solution::new(Status::NotSolved, HashMap::new());
+        let mut section = None;
+        let mut constraint_count = 0;
+        for line in BufReader::new(f).lines() {
+            let l = line.map_err(|e| e.to_string())?;
+            let l = l.trim();
+            if l.is_empty() || l.chars().all(|c| c == '=' || c == '-') {
+                continue;
+            }
+            if l.starts_with("This problem is infeasible") {
+                solution.status = Status::Infeasible;
+            } else if l.starts_with("This problem is unbounded") {
+                solution.status = Status::Unbounded;
+            } else if l.starts_with("Value of objective function:") {
+                solution.objective_value = number_after(l, "Value of objective function:");
+            } else if l.starts_with("Actual values of the variables") {
+                solution.status = Status::Optimal;
+                section = Some(Section::Variables);
+            } else if l.starts_with("Actual values of the constraints") {
+                section = Some(Section::Constraints);
+            } else if l.starts_with("Dual value") {
+                section = Some(Section::Duals(constraint_count));
+            } else {
+                // "x                               1.5"
+                let mut tokens = l.split_whitespace();
+                let value = match (tokens.next(), tokens.next().map(str::parse::<f32>)) {
+                    (Some(name), Some(Ok(value))) => (name.to_string(), value),
+                    // any other printout ends the section
+                    _ => {
+                        section = None;
+                        continue;
+                    }
+                };
+                let values = match section {
+                    Some(Section::Variables) => &mut solution.results,
+                    Some(Section::Constraints) => {
+                        constraint_count += 1;
+                        &mut solution.activities
+                    }
+                    // the constraints are listed first, then the variables with their reduced costs
+                    Some(Section::Duals(0)) => &mut solution.reduced_costs,
+                    Some(Section::Duals(ref mut constraints_left)) => {
+                        *constraints_left -= 1;
+                        &mut solution.dual_values
+                    }
+                    None => continue,
+                };
+                values.insert(value.0, value.1);
+            }
+        }
+        Ok(solution)
+    }
+}
+
+impl WithMaxSeconds<LpSolveSolver> for LpSolveSolver {
+    fn max_seconds(&self) -> Option<u32> {
+        self.seconds
+    }
+    fn with_max_seconds(&self, seconds: u32) -> LpSolveSolver {
+        LpSolveSolver {
+            seconds: Some(seconds),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithMipGap<LpSolveSolver> for LpSolveSolver {
+    fn mip_gap(&self) -> Option<f32> {
+        self.mipgap
+    }
+
+    fn with_mip_gap(&self, mipgap: f32) -> Result<LpSolveSolver, String> {
+        if mipgap.is_sign_positive() && mipgap.is_finite() {
+            Ok(LpSolveSolver {
+                mipgap: Some(mipgap),
+                ..(*self).clone()
+            })
+        } else {
+            Err("Invalid MIP gap: must be positive and finite".to_string())
+        }
+    }
+}
+
+impl SolverProgram for LpSolveSolver {
+    fn command_name(&self) -> &str {
+        &self.command_name
+    }
+
+    fn supported_features(&self) -> &[LpFeature] {
+        &[
+            LpFeature::Ranges,
+            LpFeature::Sos,
+            LpFeature::SemiContinuous,
+            LpFeature::ObjectiveConstant,
+        ]
+    }
+
+    fn arguments(&self, lp_file: &Path, _solution_file: &Path) -> Vec<OsString> {
+        // -S4 prints the constraints and the dual values along with the variables
+        let mut args = vec!["-S4".into()];
+        if let Some(seconds) = self.max_seconds() {
+            args.push("-timeout".into());
+            args.push(seconds.to_string().into());
+        }
+        if let Some(mipgap) = self.mip_gap() {
+            args.push("-gr".into());
+            args.push(mipgap.to_string().into());
+        }
+        args.push("-fmps".into());
+        args.push(lp_file.into());
+        args
+    }
+
+    fn problem_format(&self) -> ProblemFormat {
+        ProblemFormat::Mps(MpsFormat::Free)
+    }
+
+    fn preferred_temp_solution_file(&self) -> Option<&Path> {
+        self.temp_solution_file.as_deref()
+    }
+
+    fn exit_code_status(&self, code: i32) -> Option<Status> {
+        // the return value of lp_solve's solve()
+        match code {
+            0 | 9 => Some(Status::Optimal),
+            1 => Some(Status::SubOptimal),
+            2 => Some(Status::Infeasible),
+            3 => Some(Status::Unbounded),
+            // degenerate, numerical failure, user abort, timeout
+            4..=7 => Some(Status::NotSolved),
+            _ => None,
+        }
+    }
+
+    fn prints_solution(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers::{LpSolveSolver, SolverProgram, Status, WithMaxSeconds, WithMipGap};
+    use std::ffi::OsString;
+    use std::path::Path;
+
+    #[test]
+    fn cli_args_default() {
+        let solver = LpSolveSolver::new();
+        let args = solver.arguments(Path::new("test.mps"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec!["-S4".into(), "-fmps".into(), "test.mps".into()];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn cli_args_limits() {
+        let solver = LpSolveSolver::new()
+            .with_max_seconds(10)
+            .with_mip_gap(0.05)
+            .expect("mipgap should be valid");
+        let args = solver.arguments(Path::new("test.mps"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "-S4".into(),
+            "-timeout".into(),
+            "10".into(),
+            "-gr".into(),
+            "0.05".into(),
+            "-fmps".into(),
+            "test.mps".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn exit_codes() {
+        let solver = LpSolveSolver::new();
+        assert_eq!(solver.exit_code_status(0), Some(Status::Optimal));
+        assert_eq!(solver.exit_code_status(2), Some(Status::Infeasible));
+        assert_eq!(solver.exit_code_status(3), Some(Status::Unbounded));
+        assert_eq!(solver.exit_code_status(-2), None);
+    }
+}
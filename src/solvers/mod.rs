@@ -23,28 +23,63 @@
 //! The respective information is provided in the project's README in the section on
 //! [installing external solvers](https://github.com/jcavat/rust-lp-modeler#installing-external-solvers).
 
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::ffi::OsString;
 use std::fs::File;
+use std::io::Write;
 use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use crate::lp_format::LpProblem;
+use crate::lp_format::{
+    lp_string, required_features, row_names, Constraint, LpFeature, LpObjective, LpProblem,
+    OBJECTIVE_CONSTANT_VARIABLE,
+};
+use crate::lp_reader::parse_expression;
+use crate::mps_format::MpsFormat;
+use crate::problem::{LinearExpression, Problem, StrExpression};
+
+/// Concatenate the parts of a commandline argument, that can be paths
+macro_rules! format_osstr {
+    ($($parts:expr)*) => {{
+        let mut s = OsString::new();
+        $(s.push($parts);)*
+        s
+    }}
+}
 
 pub use self::auto::*;
 pub use self::cbc::*;
+#[cfg(feature = "copt")]
+pub use self::copt::*;
 #[cfg(feature = "cplex")]
 pub use self::cplex::*;
 pub use self::glpk::*;
 pub use self::gurobi::*;
+pub use self::highs::*;
+pub use self::lp_solve::*;
+#[cfg(feature = "mosek")]
+pub use self::mosek::*;
+pub use self::scip::*;
+#[cfg(feature = "xpress")]
+pub use self::xpress::*;
 
 pub mod auto;
 pub mod cbc;
+#[cfg(feature = "copt")]
+pub mod copt;
 #[cfg(feature = "cplex")]
 pub mod cplex;
 pub mod glpk;
 pub mod gurobi;
+pub mod highs;
+pub mod lp_solve;
+#[cfg(feature = "mosek")]
+pub mod mosek;
+pub mod scip;
+#[cfg(feature = "xpress")]
+pub mod xpress;
 
 /// Solution status
 #[derive(Debug, PartialEq, Clone)]
@@ -61,22 +96,63 @@ pub enum Status {
     NotSolved,
 }
 
-/// A solution to a problem
+/// A solution to a problem.
+/// Solvers may report more about their solutions in the future:
+/// create solutions with [Solution::new].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Solution {
     /// solution state
     pub status: Status,
     /// map from variable name to variable value
     pub results: HashMap<String, f32>,
+    /// value of the objective, constant included, when the solver reports it.
+    /// With several objectives, the value of the highest priority ones
+    pub objective_value: Option<f64>,
+    /// best bound on the objective, when the solver reports it
+    pub best_bound: Option<f64>,
+    /// relative gap between the objective value and the best bound, when the solver reports it
+    pub mip_gap: Option<f64>,
+    /// map from constraint name to its dual value (shadow price), for continuous problems.
+    /// Unnamed constraints are named after their index, as in the .lp file: `c0`, `c1`...
+    /// In a minimization, a binding `<=` constraint has a non-negative dual value,
+    /// and a binding `>=` constraint a non-positive one.
+    pub dual_values: HashMap<String, f32>,
+    /// map from variable name to its reduced cost, for continuous problems.
+    /// In a minimization, it is non-negative for a variable at its lower bound.
+    pub reduced_costs: HashMap<String, f32>,
+    /// map from constraint name to the value of its left hand side, without its constant
+    pub activities: HashMap<String, f32>,
+    /// map from constraint name to its right hand side minus its activity
+    pub slacks: HashMap<String, f32>,
 }
 
 impl Solution {
     /// Create a solution
     pub fn new(status: Status, results: HashMap<String, f32>) -> Solution {
-        Solution { status, results }
+        Solution {
+            status,
+            results,
+            objective_value: None,
+            best_bound: None,
+            mip_gap: None,
+            dual_values: HashMap::new(),
+            reduced_costs: HashMap::new(),
+            activities: HashMap::new(),
+            slacks: HashMap::new(),
+        }
     }
 }
 
+/// The file format in which a problem is passed to a solver program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProblemFormat {
+    /// The .lp format. See [crate::lp_format]
+    Lp,
+    /// The MPS format. See [crate::mps_format]
+    Mps(MpsFormat),
+}
+
 /// A solver that can take a problem and return a solution
 pub trait SolverTrait {
     /// Run the solver on the given problem
@@ -87,8 +163,18 @@ pub trait SolverTrait {
 pub trait SolverProgram {
     /// Returns the commandline program name
     fn command_name(&self) -> &str;
-    /// Returns the commandline arguments
+    /// Returns the commandline arguments.
+    /// `lp_file` is written in the format given by [SolverProgram::problem_format]
     fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString>;
+    /// The format of the problem file passed to the program
+    fn problem_format(&self) -> ProblemFormat {
+        ProblemFormat::Lp
+    }
+    /// The parts of the .lp format the program understands.
+    /// The others are rewritten when possible. See [LpFeature].
+    fn supported_features(&self) -> &[LpFeature] {
+        &[]
+    }
     /// If there is a predefined solution filename
     fn preferred_temp_solution_file(&self) -> Option<&Path> {
         None
@@ -97,10 +183,31 @@ pub trait SolverProgram {
     fn parse_stdout_status(&self, _stdout: &[u8]) -> Option<Status> {
         None
     }
+    /// The status a program reports with its exit code, if it does.
+    /// Other non-zero exit codes are errors
+    fn exit_code_status(&self, _code: i32) -> Option<Status> {
+        None
+    }
+    /// Whether the program prints the solution instead of writing it to the solution file.
+    /// The output is then saved to the solution file, to be parsed
+    fn prints_solution(&self) -> bool {
+        false
+    }
     /// A suffix the solution file must have
     fn solution_suffix(&self) -> Option<&str> {
         None
     }
+    /// The content of an options file, for the settings the program only takes from a file
+    fn options_file_content(&self) -> Option<String> {
+        None
+    }
+    /// The commandline arguments that pass the options file to the program
+    fn options_file_arguments(&self, _options_file: &Path) -> Vec<OsString> {
+        vec![]
+    }
+    /// Complete the solution with what the output of the program says about the objective,
+    /// such as the best bound and the final gap
+    fn parse_stdout_objective(&self, _stdout: &[u8], _solution: &mut Solution) {}
 }
 
 /// A solver that can parse a solution file
@@ -142,9 +249,22 @@ pub trait SolverWithSolutionParsing {
 impl<T: SolverWithSolutionParsing + SolverProgram> SolverTrait for T {
     fn run<'a, P: LpProblem<'a>>(&self, problem: &'a P) -> Result<Solution, String> {
         let command_name = self.command_name();
-        let file_model = problem
-            .to_tmp_file()
-            .map_err(|e| format!("Unable to create {} problem file: {}", command_name, e))?;
+        let supported = self.supported_features();
+        if let Some(feature) = required_features(problem)
+            .into_iter()
+            .find(|f| !supported.contains(f))
+        {
+            return Err(format!("{} does not support {}", command_name, feature));
+        }
+        if !supported.contains(&LpFeature::MultiObjective) && problem.objectives().next().is_some()
+        {
+            return solve_by_priority(self, problem);
+        }
+        let file_model = match self.problem_format() {
+            ProblemFormat::Lp => problem.to_tmp_file_with_features(supported),
+            ProblemFormat::Mps(format) => problem.to_tmp_mps_file(format),
+        }
+        .map_err(|e| format!("Unable to create {} problem file: {}", command_name, e))?;
 
         let temp_solution_file = if let Some(p) = self.preferred_temp_solution_file() {
             PathBuf::from(p)
@@ -155,20 +275,39 @@ impl<T: SolverWithSolutionParsing + SolverProgram> SolverTrait for T {
             }
             PathBuf::from(builder.tempfile().map_err(|e| e.to_string())?.path())
         };
-        let arguments = self.arguments(file_model.path(), &temp_solution_file);
+        let mut arguments = self.arguments(file_model.path(), &temp_solution_file);
+        // the options file has to live until the program exits
+        let _options_file = match self.options_file_content() {
+            Some(content) => {
+                let mut file = tempfile::NamedTempFile::new().map_err(|e| e.to_string())?;
+                file.write_all(content.as_bytes())
+                    .map_err(|e| format!("Unable to write {} options file: {}", command_name, e))?;
+                arguments.extend(self.options_file_arguments(file.path()));
+                Some(file)
+            }
+            None => None,
+        };
 
         let output = Command::new(command_name)
             .args(arguments)
             .output()
             .map_err(|e| format!("Error while running {}: {}", command_name, e))?;
 
-        if !output.status.success() {
-            return Err(format!(
-                "{} exited with status {}",
-                command_name, output.status
-            ));
+        let exit_status = match output.status.code().and_then(|c| self.exit_code_status(c)) {
+            Some(status) => Some(status),
+            None if output.status.success() => None,
+            None => {
+                return Err(format!(
+                    "{} exited with status {}",
+                    command_name, output.status
+                ))
+            }
+        };
+        if self.prints_solution() {
+            std::fs::write(&temp_solution_file, &output.stdout)
+                .map_err(|e| format!("Unable to write {} solution: {}", command_name, e))?;
         }
-        match self.parse_stdout_status(&output.stdout) {
+        match self.parse_stdout_status(&output.stdout).or(exit_status) {
             Some(Status::Infeasible) => Ok(Solution::new(Status::Infeasible, Default::default())),
             Some(Status::Unbounded) => Ok(Solution::new(Status::Unbounded, Default::default())),
             status_hint => {
@@ -184,12 +323,103 @@ impl<T: SolverWithSolutionParsing + SolverProgram> SolverTrait for T {
                 if let Some(status) = status_hint {
                     solution.status = status;
                 }
+                self.parse_stdout_objective(&output.stdout, &mut solution);
+                complete_activities_and_slacks(problem, &mut solution);
+                solution.results.remove(OBJECTIVE_CONSTANT_VARIABLE);
                 Ok(solution)
             }
         }
     }
 }
 
+/// Solvers report either the activities or the slacks of the constraints.
+/// Deduce the others from the right hand sides, with the constants of the left hand sides moved there.
+fn complete_activities_and_slacks<'a, P: LpProblem<'a>>(problem: &'a P, solution: &mut Solution) {
+    let names = row_names(problem, LpFeature::ALL);
+    for (name, constraint) in names.into_iter().zip(problem.constraints()) {
+        // quadratic constraints are left alone
+        let rhs = match parse_expression(&lp_string(&constraint.lhs)) {
+            Ok(lhs) => (constraint.rhs - lhs.constant()) as f32,
+            Err(_) => continue,
+        };
+        match (solution.activities.get(&name), solution.slacks.get(&name)) {
+            (Some(&activity), None) => {
+                solution.slacks.insert(name, rhs - activity);
+            }
+            (None, Some(&slack)) => {
+                solution.activities.insert(name, rhs - slack);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Emulate several objectives with one solve per priority level, from the highest.
+/// After each solve, the blended objective of the level is constrained to stay within
+/// its tolerance of the value that was found.
+/// The objective value of the solution is the one of the highest priority level, offset included.
+fn solve_by_priority<'a, S: SolverTrait, P: LpProblem<'a>>(
+    solver: &S,
+    problem: &'a P,
+) -> Result<Solution, String> {
+    let mut stage = Problem::from_lp_problem(problem);
+    let mut objectives = std::mem::take(&mut stage.objectives);
+    objectives.sort_by_key(|o| std::cmp::Reverse(o.priority));
+    let mut solution = None;
+    let mut primary = None;
+    // the stages optimize other objectives: report the one with the highest priority
+    let with_primary_value = |mut result: Solution, primary: &Option<LinearExpression>| {
+        if let Some(primary) = primary.as_ref().filter(|_| !result.results.is_empty()) {
+            let value = primary.evaluate(&result.results) + problem.objective_offset();
+            result.objective_value = Some(value);
+        }
+        result
+    };
+    for level in objectives.chunk_by(|a, b| a.priority == b.priority) {
+        let mut blended = LinearExpression::default();
+        for objective in level {
+            let expression = parse_expression(&objective.expression.0).map_err(|e| {
+                format!(
+                    "cannot optimize objective {:?} on its own: {}",
+                    objective.expression.0, e
+                )
+            })?;
+            for (name, coefficient) in expression.terms() {
+                blended.add_term(name, objective.weight * coefficient);
+            }
+            blended.set_constant(blended.constant() + objective.weight * expression.constant());
+        }
+        stage.objective = StrExpression(lp_string(&blended));
+        if primary.is_none() {
+            primary = Some(blended.clone());
+        }
+        let result = solver.run(&stage)?;
+        if result.status != Status::Optimal {
+            return Ok(with_primary_value(result, &primary));
+        }
+        let value = blended.evaluate(&result.results);
+        let tolerance = level
+            .iter()
+            .map(|o| o.abs_tol.max(o.rel_tol * value.abs()))
+            .fold(0., f64::max);
+        let (operator, bound) = match problem.sense() {
+            LpObjective::Minimize => (Ordering::Less, value + tolerance),
+            LpObjective::Maximize => (Ordering::Greater, value - tolerance),
+        };
+        let rhs = bound - blended.constant();
+        blended.set_constant(0.);
+        stage.constraints.push(Constraint {
+            lhs: StrExpression(lp_string(&blended)),
+            operator,
+            rhs,
+            name: None,
+        });
+        solution = Some(result);
+    }
+    let solution = solution.expect("the problem has objectives");
+    Ok(with_primary_value(solution, &primary))
+}
+
 /// Configure the max allowed runtime
 pub trait WithMaxSeconds<T> {
     /// get max runtime
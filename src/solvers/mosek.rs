@@ -0,0 +1,313 @@
+//! The MOSEK optimizer.
+//! You need to activate the "mosek" feature of this crate to use this solver.
+//!
+//! The `mosek` command reads the problem in the .lp format,
+//! and writes the solution in its text solution format.
+//! Integer problems have an integer solution, and continuous problems have an interior point
+//! solution and often a basic one: each goes to its own file, and the right one is read.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufRead, BufReader, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use crate::lp_format::{AsVariable, LpFeature, LpProblem};
+use crate::solvers::{
+    Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds, WithMipGap,
+    WithNbThreads,
+};
+use crate::util::number_after;
+
+/// The MOSEK optimizer
+#[derive(Debug, Clone)]
+pub struct MosekSolver {
+    command: String,
+    seconds: Option<u32>,
+    threads: Option<u32>,
+    mipgap: Option<f32>,
+}
+
+impl Default for MosekSolver {
+    fn default() -> Self {
+        Self::with_command("mosek".into())
+    }
+}
+
+impl MosekSolver {
+    /// Create a mosek solver from the given binary
+    pub fn with_command(command: String) -> Self {
+        Self {
+            command,
+            seconds: None,
+            threads: None,
+            mipgap: None,
+        }
+    }
+}
+
+impl WithMaxSeconds<MosekSolver> for MosekSolver {
+    fn max_seconds(&self) -> Option<u32> {
+        self.seconds
+    }
+    fn with_max_seconds(&self, seconds: u32) -> MosekSolver {
+        MosekSolver {
+            seconds: Some(seconds),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithNbThreads<MosekSolver> for MosekSolver {
+    fn nb_threads(&self) -> Option<u32> {
+        self.threads
+    }
+    fn with_nb_threads(&self, threads: u32) -> MosekSolver {
+        MosekSolver {
+            threads: Some(threads),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithMipGap<MosekSolver> for MosekSolver {
+    fn mip_gap(&self) -> Option<f32> {
+        self.mipgap
+    }
+
+    fn with_mip_gap(&self, mipgap: f32) -> Result<MosekSolver, String> {
+        if mipgap.is_sign_positive() && mipgap.is_finite() {
+            Ok(MosekSolver {
+                mipgap: Some(mipgap),
+                ..(*self).clone()
+            })
+        } else {
+            Err("Invalid MIP gap: must be positive and finite".to_string())
+        }
+    }
+}
+
+impl SolverProgram for MosekSolver {
+    fn command_name(&self) -> &str {
+        &self.command
+    }
+
+    fn supported_features(&self) -> &[LpFeature] {
+        &[
+            LpFeature::QuadraticObjective,
+            LpFeature::QuadraticConstraints,
+            LpFeature::ObjectiveConstant,
+        ]
+    }
+
+    fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
+        let mut args: Vec<OsString> = Vec::new();
+        let mut parameter = |name: &str, value: OsString| {
+            args.push("-d".into());
+            args.push(name.into());
+            args.push(value);
+        };
+        if let Some(seconds) = self.max_seconds() {
+            parameter("MSK_DPAR_OPTIMIZER_MAX_TIME", seconds.to_string().into());
+        }
+        if let Some(threads) = self.nb_threads() {
+            parameter("MSK_IPAR_NUM_THREADS", threads.to_string().into());
+        }
+        if let Some(mipgap) = self.mip_gap() {
+            parameter("MSK_DPAR_MIO_TOL_REL_GAP", mipgap.to_string().into());
+        }
+        for (solution, file) in solution_files(solution_file) {
+            parameter(&format!("MSK_SPAR_{}_SOL_FILE_NAME", solution), file.into());
+        }
+        args.push(lp_file.into());
+        args
+    }
+}
+
+/// The files of the integer, basic and interior point solutions, in the order they are preferred
+fn solution_files(solution_file: &Path) -> [(&'static str, PathBuf); 3] {
+    let with_extension = |extension: &str| {
+        let mut path = solution_file.as_os_str().to_owned();
+        path.push(extension);
+        PathBuf::from(path)
+    };
+    [
+        ("INT", solution_file.to_path_buf()),
+        ("BAS", with_extension(".bas")),
+        ("ITR", with_extension(".itr")),
+    ]
+}
+
+/// The part of the solution file that is being read
+enum Section {
+    Constraints,
+    Variables,
+}
+
+impl SolverWithSolutionParsing for MosekSolver {
+    fn read_solution_from_path<'a, P: LpProblem<'a>>(
+        &self,
+        temp_solution_file: &Path,
+        problem: Option<&'a P>,
+    ) -> Result<Solution, String> {
+        let [integer, basic, interior_point] = solution_files(temp_solution_file);
+        let is_integer = problem.map(|p| p.variables().any(|v| v.kind().is_integer()));
+        let candidates = match is_integer {
+            Some(true) => vec![integer],
+            Some(false) => vec![basic, interior_point],
+            None => vec![integer, basic, interior_point],
+        };
+        // the basic solution is preferred to the interior point one when both exist
+        let path = candidates
+            .iter()
+            .map(|(_, path)| path)
+            .find(|path| path.exists())
+            .unwrap_or(&candidates[0].1);
+        match File::open(path) {
+            Ok(f) => self.read_specific_solution(&f, problem),
+            // no solution was found, for instance when a limit was reached
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Ok(Solution::new(Status::NotSolved, HashMap::new()))
+            }
+            Err(e) => Err(format!("Cannot open solution file {:?}: {}", path, e)),
+        }
+    }
+
+    fn read_specific_solution<'a, P: LpProblem<'a>>(
+        &self,
+        f: &File,
+        _problem: Option<&'a P>,
+    ) -> Result<Solution, String> {
+        let mut solution = Solution::new(Status::NotSolved, HashMap::new());
+        let mut section = None;
+        for line in BufReader::new(f).lines() {
+            let l = line.map_err(|e| e.to_string())?;
+            let fields: Vec<_> = l.split_whitespace().collect();
+            match fields[..] {
+                [] | ["INDEX", ..] => continue,
+                ["CONSTRAINTS"] => section = Some(Section::Constraints),
+                ["VARIABLES"] => section = Some(Section::Variables),
+                ["SOLUTION", "STATUS", ":", status] => {
+                    solution.status = match status {
+                        "OPTIMAL" | "INTEGER_OPTIMAL" => Status::Optimal,
+                        "PRIMAL_FEASIBLE" | "PRIMAL_AND_DUAL_FEASIBLE" => Status::SubOptimal,
+                        // the certificates: "PRIM_INFEAS_CER", "DUAL_INFEAS_CER"
+                        s if s.starts_with("PRIM") && s.contains("INFEAS") => Status::Infeasible,
+                        s if s.starts_with("DUAL") && s.contains("INFEAS") => Status::Unbounded,
+                        _ => Status::NotSolved,
+                    }
+                }
+                ["PRIMAL", "OBJECTIVE", ..] => {
+                    solution.objective_value = number_after(&l, ":");
+                }
+                // "0          c0               UL 1.5          NONE         1.5          0            2"
+                [_, name, _, activity, _, _, ref duals @ ..] if section.is_some() => {
+                    let parse = |value: &str| {
+                        value
+                            .parse::<f32>()
+                            .map_err(|e| format!("Incorrect solution format: {:?}: {}", l, e))
+                    };
+                    let name = name.to_string();
+                    let duals = match duals {
+                        [lower, upper] => Some((parse(lower)?, parse(upper)?)),
+                        _ => None,
+                    };
+                    // mosek's row duals have the opposite sign of the other solvers' ones
+                    let (values, duals, dual) = match section {
+                        Some(Section::Constraints) => (
+                            &mut solution.activities,
+                            &mut solution.dual_values,
+                            duals.map(|(lower, upper)| upper - lower),
+                        ),
+                        _ => (
+                            &mut solution.results,
+                            &mut solution.reduced_costs,
+                            duals.map(|(lower, upper)| lower - upper),
+                        ),
+                    };
+                    values.insert(name.clone(), parse(activity)?);
+                    if let Some(dual) = dual {
+                        duals.insert(name, dual);
+                    }
+                }
+                // the header lines, and the sections that are not read
+                _ => section = None,
+            }
+        }
+        match solution.status {
+            // the values are a certificate, not a solution
+            Status::Infeasible | Status::Unbounded => {
+                Ok(Solution::new(solution.status, HashMap::new()))
+            }
+            _ => Ok(solution),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers::{MosekSolver, SolverProgram, WithMaxSeconds, WithMipGap, WithNbThreads};
+    use std::ffi::OsString;
+    use std::path::Path;
+
+    #[test]
+    fn cli_args_default() {
+        let solver = MosekSolver::default();
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "-d".into(),
+            "MSK_SPAR_INT_SOL_FILE_NAME".into(),
+            "test.sol".into(),
+            "-d".into(),
+            "MSK_SPAR_BAS_SOL_FILE_NAME".into(),
+            "test.sol.bas".into(),
+            "-d".into(),
+            "MSK_SPAR_ITR_SOL_FILE_NAME".into(),
+            "test.sol.itr".into(),
+            "test.lp".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn cli_args_multiple() {
+        let solver = MosekSolver::default()
+            .with_max_seconds(10)
+            .with_nb_threads(3)
+            .with_mip_gap(0.05)
+            .expect("mipgap should be valid");
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "-d".into(),
+            "MSK_DPAR_OPTIMIZER_MAX_TIME".into(),
+            "10".into(),
+            "-d".into(),
+            "MSK_IPAR_NUM_THREADS".into(),
+            "3".into(),
+            "-d".into(),
+            "MSK_DPAR_MIO_TOL_REL_GAP".into(),
+            "0.05".into(),
+            "-d".into(),
+            "MSK_SPAR_INT_SOL_FILE_NAME".into(),
+            "test.sol".into(),
+            "-d".into(),
+            "MSK_SPAR_BAS_SOL_FILE_NAME".into(),
+            "test.sol.bas".into(),
+            "-d".into(),
+            "MSK_SPAR_ITR_SOL_FILE_NAME".into(),
+            "test.sol.itr".into(),
+            "test.lp".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn cli_args_mipgap_negative() {
+        let solver = MosekSolver::default().with_mip_gap(-0.05);
+        assert!(solver.is_err());
+    }
+}
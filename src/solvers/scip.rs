@@ -0,0 +1,281 @@
+//! The SCIP optimization suite.
+//! [https://www.scipopt.org/]
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::lp_format::*;
+use crate::solvers::{
+    Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds, WithMipGap,
+    WithNbThreads,
+};
+use crate::util::number_after;
+
+/// The SCIP solver
+#[derive(Debug, Clone)]
+pub struct ScipSolver {
+    command_name: String,
+    temp_solution_file: Option<PathBuf>,
+    threads: Option<u32>,
+    seconds: Option<u32>,
+    mipgap: Option<f32>,
+}
+
+impl Default for ScipSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScipSolver {
+    /// Create a SCIP solver instance
+    pub fn new() -> ScipSolver {
+        ScipSolver {
+            command_name: "scip".to_string(),
+            temp_solution_file: None,
+            threads: None,
+            seconds: None,
+            mipgap: None,
+        }
+    }
+
+    /// set the name of the executable to use
+    pub fn command_name(&self, command_name: String) -> ScipSolver {
+        ScipSolver {
+            command_name,
+            ..(*self).clone()
+        }
+    }
+
+    /// Set the temporary solution file to use
+    pub fn with_temp_solution_file(&self, temp_solution_file: String) -> ScipSolver {
+        ScipSolver {
+            temp_solution_file: Some(temp_solution_file.into()),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl SolverWithSolutionParsing for ScipSolver {
+    fn read_specific_solution<'a, P: LpProblem<'a>>(
+        &self,
+        f: &File,
+        problem: Option<&'a P>,
+    ) -> Result<Solution, String> {
+        let mut vars_value: HashMap<String, _> = HashMap::new();
+
+        // SCIP does not write the variables that are zero
+        if let Some(p) = problem {
+            for var in p.variables() {
+                vars_value.insert(var.name().to_string(), 0.0);
+            }
+        }
+
+        let mut lines = BufReader::new(f).lines();
+        let status_line = match lines.next() {
+            Some(Ok(l)) => l,
+            _ => return Err("Incorrect solution format: No solution status found".to_string()),
+        };
+        let status = match status_line.strip_prefix("solution status:").map(str::trim) {
+            Some("optimal solution found") => Status::Optimal,
+            Some("infeasible") => Status::Infeasible,
+            Some("infeasible or unbounded") => Status::NotSolved,
+            Some("unbounded") => Status::Unbounded,
+            // "gap limit reached", "time limit reached", and the other limits
+            Some(_) => Status::SubOptimal,
+            None => return Err("Incorrect solution format: No solution status found".to_string()),
+        };
+        let mut objective_value = None;
+        for line in lines {
+            let l = line.map_err(|e| e.to_string())?;
+            if l.starts_with("objective value:") {
+                objective_value = number_after(&l, "objective value:");
+                continue;
+            }
+            if l == "no solution available" {
+                let status = match status {
+                    Status::SubOptimal => Status::NotSolved,
+                    status => status,
+                };
+                return Ok(Solution::new(status, HashMap::new()));
+            }
+            // "x                                 5 	(obj:-10)"
+            let result_line: Vec<_> = l.split_whitespace().collect();
+            if result_line.len() >= 2 {
+                match result_line[1].parse::<f32>() {
+                    Ok(n) => {
+                        vars_value.insert(result_line[0].to_string(), n);
+                    }
+                    Err(e) => return Err(e.to_string()),
+                }
+            } else {
+                return Err("Incorrect solution format".to_string());
+            }
+        }
+        Ok(Solution {
+            objective_value,
+            ..Solution::new(status, vars_value)
+        })
+    }
+}
+
+impl WithMaxSeconds<ScipSolver> for ScipSolver {
+    fn max_seconds(&self) -> Option<u32> {
+        self.seconds
+    }
+    fn with_max_seconds(&self, seconds: u32) -> ScipSolver {
+        ScipSolver {
+            seconds: Some(seconds),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithNbThreads<ScipSolver> for ScipSolver {
+    fn nb_threads(&self) -> Option<u32> {
+        self.threads
+    }
+    fn with_nb_threads(&self, threads: u32) -> ScipSolver {
+        ScipSolver {
+            threads: Some(threads),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithMipGap<ScipSolver> for ScipSolver {
+    fn mip_gap(&self) -> Option<f32> {
+        self.mipgap
+    }
+
+    fn with_mip_gap(&self, mipgap: f32) -> Result<ScipSolver, String> {
+        if mipgap.is_sign_positive() && mipgap.is_finite() {
+            Ok(ScipSolver {
+                mipgap: Some(mipgap),
+                ..(*self).clone()
+            })
+        } else {
+            Err("Invalid MIP gap: must be positive and finite".to_string())
+        }
+    }
+}
+
+impl SolverProgram for ScipSolver {
+    fn command_name(&self) -> &str {
+        &self.command_name
+    }
+
+    fn supported_features(&self) -> &[LpFeature] {
+        &[
+            LpFeature::Ranges,
+            LpFeature::QuadraticObjective,
+            LpFeature::QuadraticConstraints,
+            LpFeature::Sos,
+            LpFeature::SemiContinuous,
+            LpFeature::Indicators,
+            LpFeature::ObjectiveConstant,
+        ]
+    }
+
+    fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
+        let mut commands = vec![format_osstr!("read \"" lp_file "\"")];
+        if let Some(seconds) = self.max_seconds() {
+            commands.push(format_osstr!("set limits time " seconds.to_string()));
+        }
+        if let Some(mipgap) = self.mip_gap() {
+            commands.push(format_osstr!("set limits gap " mipgap.to_string()));
+        }
+        if let Some(threads) = self.nb_threads() {
+            // the threads of the concurrent solvers, and of the lp solver
+            commands.push(format_osstr!("set parallel maxnthreads " threads.to_string()));
+            commands.push(format_osstr!("set lp threads " threads.to_string()));
+        }
+        commands.push("optimize".into());
+        commands.push(format_osstr!("write solution \"" solution_file "\""));
+        commands.push("quit".into());
+        commands
+            .into_iter()
+            .flat_map(|command| vec!["-c".into(), command])
+            .collect()
+    }
+
+    fn preferred_temp_solution_file(&self) -> Option<&Path> {
+        self.temp_solution_file.as_deref()
+    }
+
+    fn parse_stdout_objective(&self, stdout: &[u8], solution: &mut Solution) {
+        // "Dual Bound         : -1.70000000000000e+02" and "Gap                : 0.00 %"
+        let stdout = String::from_utf8_lossy(stdout);
+        let statistic = |name: &str| {
+            let line = stdout.lines().rev().find(|l| l.starts_with(name))?;
+            number_after(line, ":")
+        };
+        solution.best_bound = statistic("Dual Bound");
+        solution.mip_gap = statistic("Gap").map(|percent| percent / 100.);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers::{ScipSolver, SolverProgram, WithMaxSeconds, WithMipGap, WithNbThreads};
+    use std::ffi::OsString;
+    use std::path::Path;
+
+    #[test]
+    fn cli_args_default() {
+        let solver = ScipSolver::new();
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "-c".into(),
+            "read \"test.lp\"".into(),
+            "-c".into(),
+            "optimize".into(),
+            "-c".into(),
+            "write solution \"test.sol\"".into(),
+            "-c".into(),
+            "quit".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn cli_args_multiple() {
+        let solver = ScipSolver::new()
+            .with_max_seconds(10)
+            .with_nb_threads(3)
+            .with_mip_gap(0.05)
+            .expect("mipgap should be valid");
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.sol"));
+
+        let expected: Vec<OsString> = vec![
+            "-c".into(),
+            "read \"test.lp\"".into(),
+            "-c".into(),
+            "set limits time 10".into(),
+            "-c".into(),
+            "set limits gap 0.05".into(),
+            "-c".into(),
+            "set parallel maxnthreads 3".into(),
+            "-c".into(),
+            "set lp threads 3".into(),
+            "-c".into(),
+            "optimize".into(),
+            "-c".into(),
+            "write solution \"test.sol\"".into(),
+            "-c".into(),
+            "quit".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn cli_args_mipgap_negative() {
+        let solver = ScipSolver::new().with_mip_gap(-0.05);
+        assert!(solver.is_err());
+    }
+}
@@ -0,0 +1,240 @@
+//! The FICO Xpress optimizer.
+//! You need to activate the "xpress" feature of this crate to use this solver.
+//!
+//! The `optimizer` console reads the problem in the .lp format,
+//! and writes the solution in its ASCII (.prt) format.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::lp_format::{LpFeature, LpProblem};
+use crate::solvers::{
+    Solution, SolverProgram, SolverWithSolutionParsing, Status, WithMaxSeconds, WithMipGap,
+    WithNbThreads,
+};
+use crate::util::number_after;
+
+/// FICO Xpress optimizer
+#[derive(Debug, Clone)]
+pub struct XpressSolver {
+    command: String,
+    seconds: Option<u32>,
+    threads: Option<u32>,
+    mipgap: Option<f32>,
+}
+
+impl Default for XpressSolver {
+    fn default() -> Self {
+        Self::with_command("optimizer".into())
+    }
+}
+
+impl XpressSolver {
+    /// Create an xpress solver from the given binary
+    pub fn with_command(command: String) -> Self {
+        Self {
+            command,
+            seconds: None,
+            threads: None,
+            mipgap: None,
+        }
+    }
+}
+
+impl WithMaxSeconds<XpressSolver> for XpressSolver {
+    fn max_seconds(&self) -> Option<u32> {
+        self.seconds
+    }
+    fn with_max_seconds(&self, seconds: u32) -> XpressSolver {
+        XpressSolver {
+            seconds: Some(seconds),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithNbThreads<XpressSolver> for XpressSolver {
+    fn nb_threads(&self) -> Option<u32> {
+        self.threads
+    }
+    fn with_nb_threads(&self, threads: u32) -> XpressSolver {
+        XpressSolver {
+            threads: Some(threads),
+            ..(*self).clone()
+        }
+    }
+}
+
+impl WithMipGap<XpressSolver> for XpressSolver {
+    fn mip_gap(&self) -> Option<f32> {
+        self.mipgap
+    }
+
+    fn with_mip_gap(&self, mipgap: f32) -> Result<XpressSolver, String> {
+        if mipgap.is_sign_positive() && mipgap.is_finite() {
+            Ok(XpressSolver {
+                mipgap: Some(mipgap),
+                ..(*self).clone()
+            })
+        } else {
+            Err("Invalid MIP gap: must be positive and finite".to_string())
+        }
+    }
+}
+
+impl SolverProgram for XpressSolver {
+    fn command_name(&self) -> &str {
+        &self.command
+    }
+
+    fn supported_features(&self) -> &[LpFeature] {
+        &[
+            LpFeature::QuadraticObjective,
+            LpFeature::QuadraticConstraints,
+            LpFeature::Sos,
+            LpFeature::SemiContinuous,
+            LpFeature::Indicators,
+            LpFeature::ObjectiveConstant,
+        ]
+    }
+
+    fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
+        // each argument is a console command
+        let mut args = vec![format_osstr!("READPROB \"" lp_file "\"")];
+
+        if let Some(seconds) = self.max_seconds() {
+            args.push(format_osstr!("MAXTIME=" seconds.to_string()));
+        }
+        if let Some(threads) = self.nb_threads() {
+            args.push(format_osstr!("THREADS=" threads.to_string()));
+        }
+        if let Some(mipgap) = self.mip_gap() {
+            args.push(format_osstr!("MIPRELSTOP=" mipgap.to_string()));
+        }
+
+        args.push("OPTIMIZE".into());
+        args.push(format_osstr!("WRITEPRTSOL \"" solution_file "\""));
+        args.push("QUIT".into());
+
+        args
+    }
+
+    fn solution_suffix(&self) -> Option<&str> {
+        // xpress adds this extension to file names that have none
+        Some(".prt")
+    }
+}
+
+impl SolverWithSolutionParsing for XpressSolver {
+    fn read_specific_solution<'a, P: LpProblem<'a>>(
+        &self,
+        f: &File,
+        _problem: Option<&'a P>,
+    ) -> Result<Solution, String> {
+        let mut solution = Solution::new(Status::NotSolved, HashMap::new());
+        // the status is only read in the statistics, where no row or column name appears
+        let mut in_statistics = false;
+        for line in BufReader::new(f).lines() {
+            let l = line.map_err(|e| e.to_string())?;
+            if l.starts_with("Solution Statistics") {
+                in_statistics = true;
+            } else if l.starts_with("Rows Section") || l.starts_with("Columns Section") {
+                in_statistics = false;
+            } else if in_statistics {
+                let lower = l.to_lowercase();
+                if lower.contains("optimal solution found") {
+                    solution.status = Status::Optimal;
+                } else if lower.contains("infeasible") {
+                    solution.status = Status::Infeasible;
+                } else if lower.contains("unbounded") {
+                    solution.status = Status::Unbounded;
+                } else if l.contains("Objective function value is") {
+                    solution.objective_value = number_after(&l, "Objective function value is");
+                } else if l.contains("Best bound is") {
+                    solution.best_bound = number_after(&l, "Best bound is");
+                }
+            }
+            let fields: Vec<_> = l.split_whitespace().collect();
+            let parse = |i: usize| {
+                fields[i]
+                    .parse::<f32>()
+                    .map_err(|e| format!("Incorrect solution format: {:?}: {}", l, e))
+            };
+            match fields.first() {
+                // " C      4  x              BS       1.500000      1.000000      .000000"
+                Some(&"C") if fields.len() >= 7 => {
+                    solution.results.insert(fields[2].to_string(), parse(4)?);
+                    solution
+                        .reduced_costs
+                        .insert(fields[2].to_string(), parse(6)?);
+                }
+                // " L      2  c0             UL       1.500000       .000000     2.000000     1.500000"
+                Some(&"L") | Some(&"G") | Some(&"E") | Some(&"R") if fields.len() >= 8 => {
+                    let name = fields[2].to_string();
+                    solution.activities.insert(name.clone(), parse(4)?);
+                    solution.slacks.insert(name.clone(), parse(5)?);
+                    solution.dual_values.insert(name, parse(6)?);
+                }
+                _ => {}
+            }
+        }
+        if solution.status == Status::NotSolved && !solution.results.is_empty() {
+            // a limit was reached
+            solution.status = Status::SubOptimal;
+        }
+        Ok(solution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solvers::{SolverProgram, WithMaxSeconds, WithMipGap, WithNbThreads, XpressSolver};
+    use std::ffi::OsString;
+    use std::path::Path;
+
+    #[test]
+    fn cli_args_default() {
+        let solver = XpressSolver::default();
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.prt"));
+
+        let expected: Vec<OsString> = vec![
+            "READPROB \"test.lp\"".into(),
+            "OPTIMIZE".into(),
+            "WRITEPRTSOL \"test.prt\"".into(),
+            "QUIT".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn cli_args_multiple() {
+        let solver = XpressSolver::default()
+            .with_max_seconds(10)
+            .with_nb_threads(3)
+            .with_mip_gap(0.05)
+            .expect("mipgap should be valid");
+        let args = solver.arguments(Path::new("test.lp"), Path::new("test.prt"));
+
+        let expected: Vec<OsString> = vec![
+            "READPROB \"test.lp\"".into(),
+            "MAXTIME=10".into(),
+            "THREADS=3".into(),
+            "MIPRELSTOP=0.05".into(),
+            "OPTIMIZE".into(),
+            "WRITEPRTSOL \"test.prt\"".into(),
+            "QUIT".into(),
+        ];
+
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn cli_args_mipgap_negative() {
+        let solver = XpressSolver::default().with_mip_gap(-0.05);
+        assert!(solver.is_err());
+    }
+}
@@ -35,7 +35,7 @@ impl UniqueNameGenerator {
     }
 }
 
-fn stem(name: &str) -> Cow<str> {
+fn stem(name: &str) -> Cow<'_, str> {
     if name.contains(|c: char| !c.is_ascii_alphabetic()) || name.is_empty() {
         let mut owned = name.replace(|c: char| !c.is_ascii_alphabetic(), "");
         if owned.is_empty() {
@@ -53,6 +53,17 @@ fn calculate_hash(t: &str) -> u64 {
     s.finish()
 }
 
+/// The number that follows the last occurrence of `prefix` in `text`, ignoring a trailing `,` or `%`
+pub(crate) fn number_after(text: &str, prefix: &str) -> Option<f64> {
+    let start = text.rfind(prefix)? + prefix.len();
+    text[start..]
+        .split_whitespace()
+        .next()?
+        .trim_end_matches([',', '%'])
+        .parse()
+        .ok()
+}
+
 pub(crate) fn buf_contains(haystack: &[u8], needle: &str) -> bool {
     let needle = needle.as_bytes();
     haystack
@@ -31,30 +31,20 @@ fn solve_integer_problem_with_cplex() {
 }
 
 fn solve_integer_problem_with_solver<S: SolverTrait>(solver: &S) {
-    let pb = Problem {
-        name: "int_problem".to_string(),
-        sense: LpObjective::Maximize,
-        objective: StrExpression("x - y".to_string()),
-        variables: vec![
-            Variable {
-                name: "x".to_string(),
-                is_integer: true,
-                lower_bound: -10.,
-                upper_bound: -1.,
-            },
-            Variable {
-                name: "y".to_string(),
-                is_integer: true,
-                lower_bound: 4.,
-                upper_bound: 7.,
-            },
+    let pb = Problem::new(
+        "int_problem",
+        LpObjective::Maximize,
+        StrExpression("x - y".to_string()),
+        vec![
+            Variable::new("x", true, -10., -1.),
+            Variable::new("y", true, 4., 7.),
         ],
-        constraints: vec![Constraint {
-            lhs: StrExpression("x - y".to_string()),
-            operator: Ordering::Less,
-            rhs: -4.5,
-        }],
-    };
+        vec![Constraint::new(
+            StrExpression("x - y".to_string()),
+            Ordering::Less,
+            -4.5,
+        )],
+    );
     let solution = solver.run(&pb).expect("Failed to run solver");
     assert_eq!(solution.status, Optimal);
     let expected_results: HashMap<String, f32> =
@@ -65,22 +55,17 @@ fn solve_integer_problem_with_solver<S: SolverTrait>(solver: &S) {
 }
 
 fn infeasible<S: SolverTrait>(solver: &S) {
-    let pb = Problem {
-        name: "impossible".to_string(),
-        sense: LpObjective::Maximize,
-        objective: StrExpression("x".to_string()),
-        variables: vec![Variable {
-            name: "x".to_string(),
-            is_integer: false,
-            lower_bound: 0.,
-            upper_bound: 100.,
-        }],
-        constraints: vec![Constraint {
-            lhs: StrExpression("x".to_string()),
-            operator: Ordering::Less,
-            rhs: -5.,
-        }],
-    };
+    let pb = Problem::new(
+        "impossible",
+        LpObjective::Maximize,
+        StrExpression("x".to_string()),
+        vec![Variable::new("x", false, 0., 100.)],
+        vec![Constraint::new(
+            StrExpression("x".to_string()),
+            Ordering::Less,
+            -5.,
+        )],
+    );
     let solution = solver.run(&pb).expect("Failed to run solver");
     assert_eq!(solution.status, Infeasible);
 }
@@ -1,40 +1,31 @@
 use std::cmp::Ordering;
 
-use lp_solvers::lp_format::{Constraint, LpObjective, LpProblem};
-use lp_solvers::problem::{Problem, StrExpression, Variable};
+use lp_solvers::lp_format::{
+    required_features, AsVariable, Constraint, GeneralConstraint, GeneralFunction,
+    IndicatorConstraint, LpFeature, LpObjective, LpProblem, Objective, RangeConstraint,
+    SosConstraint, SosType, VariableKind, WriteToLpFileFormat,
+};
+use lp_solvers::problem::{
+    LinearExpression, Problem, QuadraticExpression, StrExpression, Variable,
+};
 
 #[test]
 fn simple_problem() {
-    let pb = Problem {
-        name: "my_problem".to_string(),
-        sense: LpObjective::Minimize,
-        objective: StrExpression("2 x + y".to_string()),
-        variables: vec![
-            Variable {
-                name: "x".to_string(),
-                is_integer: false,
-                lower_bound: f64::NEG_INFINITY,
-                upper_bound: f64::INFINITY,
-            },
-            Variable {
-                name: "y".to_string(),
-                is_integer: false,
-                lower_bound: 0.0,
-                upper_bound: f64::INFINITY,
-            },
-            Variable {
-                name: "z".to_string(),
-                is_integer: false,
-                lower_bound: 1.,
-                upper_bound: 10.,
-            },
+    let pb = Problem::new(
+        "my_problem",
+        LpObjective::Minimize,
+        StrExpression("2 x + y".to_string()),
+        vec![
+            Variable::new("x", false, f64::NEG_INFINITY, f64::INFINITY),
+            Variable::new("y", false, 0.0, f64::INFINITY),
+            Variable::new("z", false, 1., 10.),
         ],
-        constraints: vec![Constraint {
-            lhs: StrExpression("x + y + z".to_string()),
-            operator: Ordering::Greater,
-            rhs: 5.0,
-        }],
-    };
+        vec![Constraint::new(
+            StrExpression("x + y + z".to_string()),
+            Ordering::Greater,
+            5.0,
+        )],
+    );
     let expected_str = "\\ my_problem
 
 Minimize
@@ -55,30 +46,20 @@ End
 
 #[test]
 fn with_integers() {
-    let pb = Problem {
-        name: "int_problem".to_string(),
-        sense: LpObjective::Maximize,
-        objective: StrExpression("x - y".to_string()),
-        variables: vec![
-            Variable {
-                name: "x".to_string(),
-                is_integer: true,
-                lower_bound: -10.,
-                upper_bound: 10.,
-            },
-            Variable {
-                name: "y".to_string(),
-                is_integer: true,
-                lower_bound: f64::NEG_INFINITY,
-                upper_bound: 16.5,
-            },
+    let pb = Problem::new(
+        "int_problem",
+        LpObjective::Maximize,
+        StrExpression("x - y".to_string()),
+        vec![
+            Variable::new("x", true, -10., 10.),
+            Variable::new("y", true, f64::NEG_INFINITY, 16.5),
         ],
-        constraints: vec![Constraint {
-            lhs: StrExpression("x - y".to_string()),
-            operator: Ordering::Less,
-            rhs: -5.0,
-        }],
-    };
+        vec![Constraint::new(
+            StrExpression("x - y".to_string()),
+            Ordering::Less,
+            -5.0,
+        )],
+    );
     let expected_str = "\\ int_problem
 
 Maximize
@@ -89,7 +70,7 @@ Subject To
 
 Bounds
   -10 <= x <= 10
-  y <= 16.5
+  -inf <= y <= 16.5
 
 Generals
   x
@@ -102,18 +83,13 @@ End
 
 #[test]
 fn without_constraints() {
-    let pb = Problem {
-        name: "int_problem".to_string(),
-        sense: LpObjective::Maximize,
-        objective: StrExpression("x".to_string()),
-        variables: vec![Variable {
-            name: "x".to_string(),
-            is_integer: true,
-            lower_bound: 0.,
-            upper_bound: 2.5,
-        }],
-        constraints: vec![],
-    };
+    let pb = Problem::new(
+        "int_problem",
+        LpObjective::Maximize,
+        StrExpression("x".to_string()),
+        vec![Variable::new("x", true, 0., 2.5)],
+        vec![],
+    );
     let expected_str = "\\ int_problem
 
 Maximize
@@ -131,3 +107,625 @@ End
 ";
     assert_eq!(pb.display_lp().to_string(), expected_str);
 }
+
+#[test]
+fn linear_expressions() {
+    let pb: Problem = Problem::new(
+        "linear",
+        LpObjective::Minimize,
+        LinearExpression::new(vec![("x", -1.), ("y", 2.5e-7), ("z", 0.)], -3.),
+        vec![],
+        vec![Constraint::new(
+            LinearExpression::new(vec![("x", 1.), ("y", -2.), ("x", 1.), ("z", 1e20)], 0.),
+            Ordering::Equal,
+            1e-9,
+        )],
+    );
+    let expected_str = "\\ linear
+
+Minimize
+  obj: -x + 2.5e-7 y - 3
+
+Subject To
+  c0: 2 x - 2 y + 1e20 z = 1e-9
+
+Bounds
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+}
+
+#[test]
+fn named_constraints() {
+    let constraint = |name: Option<&str>| {
+        let constraint = Constraint::new(StrExpression("x".to_string()), Ordering::Less, 1.);
+        match name {
+            Some(name) => constraint.named(name),
+            None => constraint,
+        }
+    };
+    let pb: Problem<StrExpression> = Problem::new(
+        "named",
+        LpObjective::Minimize,
+        StrExpression("x".to_string()),
+        vec![],
+        vec![
+            constraint(Some("capacity")),
+            constraint(None),
+            constraint(Some("capacity")),
+            constraint(Some("2 much")),
+            constraint(Some("obj")),
+            constraint(Some("c1")),
+        ],
+    );
+    let expected_str = "\\ named
+
+Minimize
+  obj: x
+
+Subject To
+  capacity: x <= 1
+  c1_2: x <= 1
+  capacity_2: x <= 1
+  c2_much: x <= 1
+  obj_2: x <= 1
+  c1: x <= 1
+
+Bounds
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+}
+
+#[test]
+fn ranges() {
+    let range = |lower: f64, upper: f64| RangeConstraint {
+        lhs: StrExpression("x + y".to_string()),
+        lower,
+        upper,
+        name: None,
+    };
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.name = "ranges".to_string();
+    pb.objective = StrExpression("x".to_string());
+    pb.ranges = vec![
+        range(-1., 2.).named("two_sided"),
+        range(f64::NEG_INFINITY, 3.),
+        range(4., 4.),
+        range(0., f64::INFINITY),
+    ];
+    let expected_str = "\\ ranges
+
+Minimize
+  obj: x
+
+Subject To
+  two_sided: -1 <= x + y <= 2
+  r1: x + y <= 3
+  r2: x + y = 4
+  r3: x + y >= 0
+
+Bounds
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+
+    let file = pb.to_tmp_file_with_features(&[]).unwrap();
+    let split = std::fs::read_to_string(file.path()).unwrap();
+    assert!(split.contains(
+        "  two_sided_lo: x + y >= -1
+  two_sided_hi: x + y <= 2
+  r1: x + y <= 3
+"
+    ));
+}
+
+#[test]
+fn quadratic_objective() {
+    let mut objective = QuadraticExpression::from(LinearExpression::new(vec![("x", 1.)], 0.));
+    objective.add_quadratic_term("x", "x", 1.5);
+    objective.add_quadratic_term("x", "y", -0.5);
+    objective.add_quadratic_term("y", "x", -0.5);
+    let mut pb: Problem<QuadraticExpression> = Problem::default();
+    pb.name = "qp".to_string();
+    pb.objective = objective;
+    pb.constraints = vec![Constraint::new(
+        LinearExpression::new(vec![("x", 1.), ("y", 1.)], 0.).into(),
+        Ordering::Equal,
+        1.,
+    )];
+    let expected_str = "\\ qp
+
+Minimize
+  obj: x + [ 3 x ^ 2 - 2 x * y ] / 2
+
+Subject To
+  c0: x + y = 1
+
+Bounds
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+    assert_eq!(required_features(&pb), vec![LpFeature::QuadraticObjective]);
+    assert_eq!(
+        pb.objective.quadratic_terms().collect::<Vec<_>>(),
+        vec![("x", "x", 1.5), ("x", "y", -1.)]
+    );
+}
+
+/// An expression type that only knows how to write itself
+struct Written(&'static str);
+
+impl WriteToLpFileFormat for Written {
+    fn to_lp_file_format(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[test]
+fn quadratic_expressions_are_detected() {
+    assert!(StrExpression("x + [ x * y ]".to_string()).is_quadratic());
+    assert!(!StrExpression("x + y".to_string()).is_quadratic());
+    assert!(!LinearExpression::new(vec![("x", 1.)], 0.).is_quadratic());
+    // the other expression types are written to find out
+    assert!(Written("[ x ^ 2 ]").is_quadratic());
+    assert!(!Written("x").is_quadratic());
+    let constraint = Constraint::new(Written("[ x ^ 2 ]"), Ordering::Less, 1.);
+    assert!(constraint.is_quadratic());
+    assert!(constraint.when("b", true).is_quadratic());
+}
+
+#[test]
+fn quadratic_terms_are_pairs_of_names() {
+    let mut expr = QuadraticExpression::default();
+    expr.add_quadratic_term("y", "x", 1.);
+    expr.add_quadratic_term("x", "y", 2.);
+    // names are never split again, whatever they contain
+    expr.add_quadratic_term("a*b", "c^2", 1.);
+    expr.add_quadratic_term("c^2", "c^2", 1.);
+    expr.add_quadratic_term("z", "x", 1.);
+    expr.add_quadratic_term("x", "z", -1.);
+    assert_eq!(
+        expr.quadratic_terms().collect::<Vec<_>>(),
+        vec![("a*b", "c^2", 1.), ("c^2", "c^2", 1.), ("x", "y", 3.)]
+    );
+    assert_eq!(expr.to_string(), "[ a*b * c^2 + c^2 ^ 2 + 3 x * y ]");
+
+    let mut reversed = QuadraticExpression::default();
+    reversed.add_quadratic_term("c^2", "c^2", 1.);
+    reversed.add_quadratic_term("x", "y", 3.);
+    reversed.add_quadratic_term("c^2", "a*b", 1.);
+    assert_eq!(expr, reversed);
+}
+
+#[test]
+fn quadratic_constraints() {
+    let mut risk = QuadraticExpression::from(LinearExpression::new(vec![("r", -1.)], 0.));
+    risk.add_quadratic_term("x", "y", 0.5);
+    let mut pb: Problem<QuadraticExpression> = Problem::default();
+    pb.name = "qcp".to_string();
+    pb.objective = LinearExpression::new(vec![("t", 1.)], 0.).into();
+    pb.constraints = vec![
+        QuadraticExpression::second_order_cone("t", &["x", "y"]).named("cone"),
+        Constraint::new(risk, Ordering::Less, 2.),
+    ];
+    let expected_str = "\\ qcp
+
+Minimize
+  obj: t
+
+Subject To
+  cone: [ -t ^ 2 + x ^ 2 + y ^ 2 ] <= 0
+  c1: -r + [ 0.5 x * y ] <= 2
+
+Bounds
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+    assert_eq!(
+        required_features(&pb),
+        vec![LpFeature::QuadraticConstraints]
+    );
+}
+
+#[test]
+fn special_ordered_sets() {
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.name = "sos".to_string();
+    pb.objective = StrExpression("x".to_string());
+    pb.sos = vec![
+        SosConstraint {
+            name: None,
+            kind: SosType::S1,
+            variables: vec![("x".to_string(), 1.), ("y".to_string(), 2.)],
+            priority: Some(3),
+        },
+        SosConstraint {
+            name: Some("pwl".to_string()),
+            kind: SosType::S2,
+            variables: vec![("a".to_string(), 0.5), ("b".to_string(), 1.5)],
+            priority: None,
+        },
+    ];
+    let expected_str = "\\ sos
+
+Minimize
+  obj: x
+
+Subject To
+
+Bounds
+
+SOS
+  s0: S1:: x:1 y:2
+  pwl: S2:: a:0.5 b:1.5
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+    assert_eq!(required_features(&pb), vec![LpFeature::Sos]);
+}
+
+#[test]
+fn variable_kinds() {
+    let variable = |name: &str, kind: VariableKind, lower_bound: f64, upper_bound: f64| {
+        Variable::new(name, kind, lower_bound, upper_bound)
+    };
+    let mut pb = Problem::default();
+    pb.name = "kinds".to_string();
+    pb.objective = StrExpression("a + b + c + d + e".to_string());
+    pb.variables = vec![
+        variable("a", VariableKind::Binary, 0., 1.),
+        variable("b", VariableKind::Binary, 1., 1.),
+        variable("c", VariableKind::Integer, 0., 10.),
+        variable("d", VariableKind::SemiContinuous, 2., 5.),
+        variable("e", VariableKind::SemiInteger, 1., 8.),
+    ];
+    let expected_str = "\\ kinds
+
+Minimize
+  obj: a + b + c + d + e
+
+Subject To
+
+Bounds
+  1 <= b <= 1
+  0 <= c <= 10
+  2 <= d <= 5
+  1 <= e <= 8
+
+Generals
+  c
+  e
+
+Binaries
+  a
+  b
+
+Semi-continuous
+  d
+  e
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+    assert_eq!(required_features(&pb), vec![LpFeature::SemiContinuous]);
+}
+
+#[test]
+fn indicator_constraints() {
+    let variable = |name: &str, kind: VariableKind, upper_bound: f64| {
+        Variable::new(name, kind, 0., upper_bound)
+    };
+    let constraint = |lhs: &str, operator: Ordering, rhs: f64| {
+        Constraint::new(StrExpression(lhs.to_string()), operator, rhs)
+    };
+    let mut pb = Problem::default();
+    pb.name = "indicators".to_string();
+    pb.objective = StrExpression("x + y".to_string());
+    pb.variables = vec![
+        variable("x", VariableKind::Continuous, 10.),
+        variable("y", VariableKind::Continuous, 5.),
+        variable("b", VariableKind::Binary, 1.),
+    ];
+    pb.indicators = vec![
+        constraint("x + y", Ordering::Less, 3.).when("b", true),
+        IndicatorConstraint {
+            indicator: "b".to_string(),
+            value: false,
+            constraint: constraint("x", Ordering::Greater, 2.),
+        }
+        .named("start"),
+        constraint("x - y", Ordering::Equal, 1.).when("b", true),
+    ];
+    let expected_str = "\\ indicators
+
+Minimize
+  obj: x + y
+
+Subject To
+  i0: b = 1 -> x + y <= 3
+  start: b = 0 -> x >= 2
+  i2: b = 1 -> x - y = 1
+
+Bounds
+  0 <= x <= 10
+  0 <= y <= 5
+
+Binaries
+  b
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+    assert!(required_features(&pb).is_empty());
+
+    let file = pb.to_tmp_file_with_features(&[]).unwrap();
+    let big_m = std::fs::read_to_string(file.path()).unwrap();
+    assert!(big_m.contains(
+        "Subject To
+  i0: x + y + 12 b <= 15
+  start: x + 2 b >= 2
+  i2_lo: x - y - 6 b >= -5
+  i2_hi: x - y + 9 b <= 10
+"
+    ));
+}
+
+#[test]
+fn indicator_constraints_without_big_m() {
+    let mut pb = Problem::default();
+    pb.name = "unbounded_indicator".to_string();
+    pb.objective = StrExpression("x".to_string());
+    pb.variables = vec![Variable::new("b", VariableKind::Binary, 0., 1.)];
+    pb.indicators =
+        vec![Constraint::new(StrExpression("x".to_string()), Ordering::Less, 3.).when("b", true)];
+    // the indicator is written as it is
+    assert!(pb.display_lp().to_string().contains("i0: b = 1 -> x <= 3"));
+    // x has no upper bound: there is no big-M, and the error is returned before writing
+    let err = pb.to_tmp_file_with_features(&[]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert!(err.to_string().contains("x is unbounded"), "{}", err);
+}
+
+/// A variable type that only gives its kind
+struct KindOnly(&'static str, VariableKind);
+
+impl AsVariable for KindOnly {
+    fn name(&self) -> &str {
+        self.0
+    }
+    fn kind(&self) -> VariableKind {
+        self.1
+    }
+    fn lower_bound(&self) -> f64 {
+        0.
+    }
+    fn upper_bound(&self) -> f64 {
+        1.
+    }
+}
+
+/// A variable type that only says whether it is integer
+struct IntegerOnly(bool);
+
+impl AsVariable for IntegerOnly {
+    fn name(&self) -> &str {
+        "n"
+    }
+    fn is_integer(&self) -> bool {
+        self.0
+    }
+    fn lower_bound(&self) -> f64 {
+        0.
+    }
+    fn upper_bound(&self) -> f64 {
+        10.
+    }
+}
+
+#[test]
+fn variable_kinds_and_integrality_are_derived_from_each_other() {
+    assert!(KindOnly("b", VariableKind::Binary).is_integer());
+    assert!(!KindOnly("x", VariableKind::SemiContinuous).is_integer());
+    assert_eq!(IntegerOnly(true).kind(), VariableKind::Integer);
+    assert_eq!(IntegerOnly(false).kind(), VariableKind::Continuous);
+
+    // the big-M rewriting checks that the indicator is binary with its kind
+    let mut pb: Problem<StrExpression, KindOnly> = Problem::default();
+    pb.objective = StrExpression("x".to_string());
+    pb.variables = vec![
+        KindOnly("x", VariableKind::Continuous),
+        KindOnly("b", VariableKind::Binary),
+    ];
+    pb.indicators =
+        vec![Constraint::new(StrExpression("x".to_string()), Ordering::Less, 0.5).when("b", true)];
+    let file = pb.to_tmp_file_with_features(&[]).unwrap();
+    let big_m = std::fs::read_to_string(file.path()).unwrap();
+    assert!(big_m.contains("i0: x + 0.5 b <= 1"), "{}", big_m);
+}
+
+#[test]
+fn general_constraints() {
+    let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.name = "general".to_string();
+    pb.objective = StrExpression("r".to_string());
+    pb.general_constraints = vec![
+        GeneralConstraint::new(
+            "r",
+            GeneralFunction::Max {
+                variables: names(&["x", "y"]),
+                constant: Some(2.5),
+            },
+        )
+        .named("peak"),
+        GeneralConstraint::new(
+            "s",
+            GeneralFunction::Min {
+                variables: names(&["x", "y"]),
+                constant: None,
+            },
+        ),
+        GeneralConstraint::new("a", GeneralFunction::Abs("x".to_string())),
+        GeneralConstraint::new("both", GeneralFunction::And(names(&["b1", "b2"]))),
+        GeneralConstraint::new("any", GeneralFunction::Or(names(&["b1", "b2"]))),
+        GeneralConstraint::new(
+            "cost",
+            GeneralFunction::Pwl {
+                variable: "q".to_string(),
+                points: vec![(0., 0.), (10., 5.), (20., 7.5)],
+            },
+        ),
+    ];
+    let expected_str = "\\ general
+
+Minimize
+  obj: r
+
+Subject To
+
+Bounds
+
+General Constraints
+  peak: r = MAX ( x , y , 2.5 )
+  g1: s = MIN ( x , y )
+  g2: a = ABS ( x )
+  g3: both = AND ( b1 , b2 )
+  g4: any = OR ( b1 , b2 )
+  g5: cost = PWL ( q ) : ( 0 , 0 ) ( 10 , 5 ) ( 20 , 7.5 )
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+    assert_eq!(required_features(&pb), vec![LpFeature::GeneralConstraints]);
+}
+
+#[test]
+fn multiple_objectives() {
+    let objective = |expression: &str, priority: i32| {
+        Objective::new(StrExpression(expression.to_string()), priority)
+    };
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.name = "multi".to_string();
+    pb.sense = LpObjective::Minimize;
+    pb.objectives = vec![
+        Objective {
+            rel_tol: 0.05,
+            ..objective("2 x + y", 2).named("cost")
+        },
+        Objective {
+            weight: 0.5,
+            ..objective("t", 1)
+        },
+    ];
+    let expected_str = "\\ multi
+
+Minimize multi-objectives
+  cost: Priority=2 Weight=1 AbsTol=1e-6 RelTol=0.05
+    2 x + y
+  o1: Priority=1 Weight=0.5 AbsTol=1e-6 RelTol=0
+    t
+
+Subject To
+
+Bounds
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+    assert!(required_features(&pb).is_empty());
+
+    // the offset goes to each objective, and no variable holds it
+    pb.objective_offset = 1.5;
+    let file = pb
+        .to_tmp_file_with_features(&[LpFeature::MultiObjective])
+        .unwrap();
+    let offset = std::fs::read_to_string(file.path()).unwrap();
+    assert!(offset.contains("    2 x + y + 1.5\n"));
+    assert!(offset.contains("    t + 1.5\n"));
+    assert!(!offset.contains("__obj_constant"));
+}
+
+#[test]
+fn lazy_constraints_and_user_cuts() {
+    let constraint =
+        |lhs: &str, rhs: f64| Constraint::new(StrExpression(lhs.to_string()), Ordering::Less, rhs);
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.name = "lazy".to_string();
+    pb.objective = StrExpression("x".to_string());
+    pb.constraints = vec![constraint("x + y", 4.)];
+    pb.lazy_constraints = vec![constraint("x - y", 1.).named("rarely"), constraint("x", 3.)];
+    pb.user_cuts = vec![constraint("x + 2 y", 8.)];
+    let expected_str = "\\ lazy
+
+Minimize
+  obj: x
+
+Subject To
+  c0: x + y <= 4
+
+Lazy Constraints
+  rarely: x - y <= 1
+  l1: x <= 3
+
+User Cuts
+  u0: x + 2 y <= 8
+
+Bounds
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+
+    let file = pb
+        .to_tmp_file_with_features(&[LpFeature::UserCuts])
+        .unwrap();
+    let ordinary = std::fs::read_to_string(file.path()).unwrap();
+    assert!(ordinary.contains(
+        "Subject To
+  c0: x + y <= 4
+  rarely: x - y <= 1
+  l1: x <= 3
+
+User Cuts
+  u0: x + 2 y <= 8
+"
+    ));
+}
+
+#[test]
+fn objective_constants() {
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.name = "offset".to_string();
+    pb.objective = StrExpression("2 x + 3".to_string());
+    pb.objective_offset = -5.;
+    pb.constraints = vec![Constraint::new(
+        StrExpression("x".to_string()),
+        Ordering::Greater,
+        1.,
+    )];
+    let expected_str = "\\ offset
+
+Minimize
+  obj: 2 x + 3 - 5
+
+Subject To
+  c0: x >= 1
+
+Bounds
+
+End
+";
+    assert_eq!(pb.display_lp().to_string(), expected_str);
+
+    let file = pb.to_tmp_file_with_features(&[]).unwrap();
+    let dummy = std::fs::read_to_string(file.path()).unwrap();
+    assert!(dummy.contains("  obj: 2 x - 2 __obj_constant\n"));
+    assert!(dummy.contains("Bounds\n  __obj_constant = 1\n"));
+}
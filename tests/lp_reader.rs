@@ -0,0 +1,358 @@
+use std::cmp::Ordering;
+
+use lp_solvers::lp_format::{
+    Constraint, GeneralConstraint, GeneralFunction, LpObjective, LpProblem, RangeConstraint,
+    SosConstraint, SosType, VariableKind,
+};
+use lp_solvers::lp_reader::{parse_lp, ParseError};
+use lp_solvers::problem::{Problem, QuadraticExpression, StrExpression, Variable};
+
+#[test]
+fn all_sections() {
+    let pb = parse_lp(
+        r"\ my_problem
+\* a block comment
+   spanning two lines *\
+Maximize
+  profit: 3 x + 2 y - z \ a line comment
+          + 1.5e1 w
+Subject To
+  capacity: x + y
+    + z <= 4
+  -x + 2 y >= -2
+  c2: w - 2 x - x = 0
+Bounds
+  x <= 3
+  -5 <= y <= 5
+  z free
+  w >= -inf
+Generals
+  y
+Binaries
+  b
+End
+",
+    )
+    .unwrap();
+    assert_eq!(pb.name, "my_problem");
+    assert_eq!(pb.sense, LpObjective::Maximize);
+    assert_eq!(pb.objective.to_string(), "3 x + 2 y - z + 15 w");
+    let constraints: Vec<_> = pb
+        .constraints
+        .iter()
+        .map(|c| (c.lhs.to_string(), c.operator, c.rhs))
+        .collect();
+    assert_eq!(
+        constraints,
+        vec![
+            ("x + y + z".to_string(), Ordering::Less, 4.),
+            ("-x + 2 y".to_string(), Ordering::Greater, -2.),
+            ("w - 3 x".to_string(), Ordering::Equal, 0.),
+        ]
+    );
+    let names: Vec<_> = pb.constraints.iter().map(|c| c.name.as_deref()).collect();
+    assert_eq!(names, vec![Some("capacity"), None, Some("c2")]);
+    let variables: Vec<_> = pb
+        .variables
+        .iter()
+        .map(|v| (v.name.as_str(), v.kind, v.lower_bound, v.upper_bound))
+        .collect();
+    assert_eq!(
+        variables,
+        vec![
+            ("x", VariableKind::Continuous, 0., 3.),
+            ("y", VariableKind::Integer, -5., 5.),
+            (
+                "z",
+                VariableKind::Continuous,
+                f64::NEG_INFINITY,
+                f64::INFINITY
+            ),
+            (
+                "w",
+                VariableKind::Continuous,
+                f64::NEG_INFINITY,
+                f64::INFINITY
+            ),
+            ("b", VariableKind::Binary, 0., 1.),
+        ]
+    );
+}
+
+#[test]
+fn constants_move_to_the_right_hand_side() {
+    let pb = parse_lp("min\n obj: x + 2\nst\n 3 + x + 2 <= 10\nend").unwrap();
+    assert_eq!(pb.objective.to_string(), "x + 2");
+    assert_eq!(pb.constraints[0].lhs.to_string(), "x");
+    assert_eq!(pb.constraints[0].rhs, 5.);
+}
+
+#[test]
+fn round_trip() {
+    let mut pb = Problem::new(
+        "round_trip",
+        LpObjective::Minimize,
+        StrExpression("2 x + y - 0.5 z".to_string()),
+        vec![
+            Variable::new(
+                "x",
+                VariableKind::Continuous,
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+            ),
+            Variable::new("y", VariableKind::Integer, -10., 16.5),
+            Variable::new("z", VariableKind::Continuous, f64::NEG_INFINITY, 2.),
+        ],
+        vec![
+            Constraint::new(
+                StrExpression("x + y + z".to_string()),
+                Ordering::Greater,
+                5.0,
+            ),
+            Constraint::new(StrExpression("x - y".to_string()), Ordering::Equal, -1e-7),
+        ],
+    );
+    pb.ranges = vec![RangeConstraint {
+        lhs: StrExpression("x + z".to_string()),
+        lower: -2.,
+        upper: 2.,
+        name: Some("window".to_string()),
+    }];
+    pb.sos = vec![
+        SosConstraint {
+            name: None,
+            kind: SosType::S1,
+            variables: vec![("x".to_string(), 1.), ("y".to_string(), 2.)],
+            priority: None,
+        },
+        SosConstraint {
+            name: Some("S1".to_string()),
+            kind: SosType::S2,
+            variables: vec![("s2".to_string(), 1.), ("z".to_string(), 1e-5)],
+            priority: None,
+        },
+    ];
+    pb.indicators = vec![
+        Constraint::new(StrExpression("x - 2 z".to_string()), Ordering::Less, 4.)
+            .named("switch")
+            .when("y", false),
+    ];
+    let written = pb.display_lp().to_string();
+    let read = parse_lp(&written).unwrap();
+    assert_eq!(read.display_lp().to_string(), written);
+    assert_eq!(read.sos[1], pb.sos[1]);
+    assert_eq!(read.indicators[0].indicator, "y");
+}
+
+#[test]
+fn indicator_constraints() {
+    let pb =
+        parse_lp("min\n obj: x\nst\n on: b = 1 -> x + y >= 2\n b=0->x<=0\nbin\n b\nend").unwrap();
+    let indicators: Vec<_> = pb
+        .indicators
+        .iter()
+        .map(|i| {
+            let c = &i.constraint;
+            (
+                i.indicator.as_str(),
+                i.value,
+                c.lhs.to_string(),
+                c.operator,
+                c.rhs,
+                c.name.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        indicators,
+        vec![
+            (
+                "b",
+                true,
+                "x + y".to_string(),
+                Ordering::Greater,
+                2.,
+                Some("on")
+            ),
+            ("b", false, "x".to_string(), Ordering::Less, 0., None),
+        ]
+    );
+    assert!(pb.constraints.is_empty());
+    assert_eq!(
+        parse_lp("min\n obj: x\nst\n b = 2 -> x <= 0\nend")
+            .unwrap_err()
+            .message,
+        "the value of an indicator variable must be 0 or 1"
+    );
+}
+
+#[test]
+fn ranges() {
+    let pb =
+        parse_lp("min\n obj: x\nst\n r: -1 <= x + y + 1 <= 3\n 5 >= 2 x >= -inf\n 1 + x <= 2\nend")
+            .unwrap();
+    let ranges: Vec<_> = pb
+        .ranges
+        .iter()
+        .map(|r| (r.name.as_deref(), r.lhs.to_string(), r.lower, r.upper))
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![
+            (Some("r"), "x + y".to_string(), -2., 2.),
+            (None, "2 x".to_string(), f64::NEG_INFINITY, 5.),
+        ]
+    );
+    assert_eq!(pb.constraints.len(), 1);
+    assert_eq!(
+        parse_lp("min\n obj: x\nst\n 1 <= x >= 0\nend")
+            .unwrap_err()
+            .message,
+        "both sides of a range must use <= or both must use >="
+    );
+}
+
+#[test]
+fn errors_have_positions() {
+    let err = |content: &str| parse_lp(content).unwrap_err();
+    assert_eq!(
+        err("Minimize\n obj: x\nSubject To\n c0: x + y 3\nEnd"),
+        ParseError {
+            line: 4,
+            column: 12,
+            message: "expected a comparison operator, found number 3".to_string()
+        }
+    );
+    assert_eq!(
+        err("Minimize\n obj: x\nBounds\n x <= ?\nEnd").to_string(),
+        "line 4, column 7: expected a number, found '?'"
+    );
+    assert_eq!(err("  x + y\nEnd").column, 3);
+    assert_eq!(err("Minimize\n x \\* unterminated").line, 2);
+    assert_eq!(
+        err("Subject To\n x >= 1\nEnd").message,
+        "missing objective section (Minimize or Maximize)"
+    );
+}
+
+#[test]
+fn semi_continuous_variables() {
+    let pb = parse_lp(
+        "min\n obj: x + y + z\nbounds\n 1 <= x <= 4\n y <= 6\ngenerals\n y\nsemi-continuous\n x y\nsemis\n z\nend",
+    )
+    .unwrap();
+    let kinds: Vec<_> = pb
+        .variables
+        .iter()
+        .map(|v| (v.name.as_str(), v.kind))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("x", VariableKind::SemiContinuous),
+            ("y", VariableKind::SemiInteger),
+            ("z", VariableKind::SemiContinuous),
+        ]
+    );
+}
+
+#[test]
+fn lazy_constraints_and_user_cuts() {
+    let pb = parse_lp(
+        "min\n obj: x\nst\n x + y >= 1\nlazy constraints\n l: x <= 3\nuser cuts\n x + y <= 5\nend",
+    )
+    .unwrap();
+    assert_eq!(pb.constraints.len(), 1);
+    assert_eq!(pb.lazy_constraints[0].name.as_deref(), Some("l"));
+    assert_eq!(pb.lazy_constraints[0].lhs.to_string(), "x");
+    assert_eq!(pb.user_cuts[0].rhs, 5.);
+    assert_eq!(
+        parse_lp(&pb.display_lp().to_string())
+            .unwrap()
+            .display_lp()
+            .to_string(),
+        pb.display_lp().to_string()
+    );
+}
+
+#[test]
+fn general_constraints_round_trip() {
+    let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.objective = StrExpression("r".to_string());
+    pb.general_constraints = vec![
+        GeneralConstraint::new(
+            "r",
+            GeneralFunction::Max {
+                variables: names(&["x", "y"]),
+                constant: Some(-2.5),
+            },
+        )
+        .named("peak"),
+        GeneralConstraint::new(
+            "s",
+            GeneralFunction::Min {
+                variables: names(&["x", "y"]),
+                constant: None,
+            },
+        )
+        .named("low"),
+        GeneralConstraint::new("a", GeneralFunction::Abs("x".to_string())).named("g2"),
+        GeneralConstraint::new("any", GeneralFunction::Or(names(&["b1", "b2"]))).named("g3"),
+        GeneralConstraint::new(
+            "cost",
+            GeneralFunction::Pwl {
+                variable: "q".to_string(),
+                points: vec![(0., 0.), (10., 5.), (20., 7.5)],
+            },
+        )
+        .named("g4"),
+    ];
+    let written = pb.display_lp().to_string();
+    let read = parse_lp(&written).unwrap();
+    assert_eq!(read.general_constraints, pb.general_constraints);
+    assert_eq!(
+        parse_lp("min\n obj: r\ngeneral constraints\n g: r = SUM ( x , y )\nend")
+            .unwrap_err()
+            .message,
+        "unsupported general constraint function 'SUM'"
+    );
+}
+
+#[test]
+fn variables_named_like_keywords() {
+    let variable = |name: &str, kind: VariableKind| Variable::new(name, kind, 0., 10.);
+    let mut pb = Problem::new(
+        "keywords",
+        LpObjective::Maximize,
+        StrExpression("min + bin + gen + st + end".to_string()),
+        vec![
+            variable("min", VariableKind::Continuous),
+            variable("bin", VariableKind::Binary),
+            variable("gen", VariableKind::Integer),
+            variable("st", VariableKind::Continuous),
+            variable("end", VariableKind::SemiContinuous),
+        ],
+        vec![Constraint::new(
+            StrExpression("min + st".to_string()),
+            Ordering::Less,
+            4.,
+        )],
+    );
+    pb.variables[1].upper_bound = 1.;
+    let written = pb.display_lp().to_string();
+    let read = parse_lp(&written).unwrap();
+    assert_eq!(read.variables, pb.variables);
+    assert_eq!(read.display_lp().to_string(), written);
+}
+
+#[test]
+fn quadratic_expressions_are_rejected() {
+    let mut objective = QuadraticExpression::default();
+    objective.add_quadratic_term("x", "y", 1.);
+    let mut pb: Problem<QuadraticExpression> = Problem::default();
+    pb.objective = objective;
+    let err = parse_lp(&pb.display_lp().to_string()).unwrap_err();
+    assert_eq!(err.message, "quadratic expressions are not supported");
+    assert_eq!((err.line, err.column), (4, 8));
+}
@@ -0,0 +1,208 @@
+use std::cmp::Ordering;
+
+use lp_solvers::lp_format::{
+    Constraint, GeneralFunction, LpObjective, LpProblem, Objective, SosConstraint, SosType,
+    VariableKind,
+};
+use lp_solvers::model::{Expr, ProblemBuilder};
+use lp_solvers::problem::Variable;
+
+#[test]
+fn operators() {
+    let mut builder = ProblemBuilder::new("operators");
+    let x = builder.continuous("x", 0., 1.);
+    let y = builder.continuous("y", 0., 1.);
+    let mut e = 2. * x - y * 3. + 1.;
+    e += x;
+    e -= 4.;
+    let pb = {
+        builder.minimize(-e + y);
+        builder.build()
+    };
+    assert_eq!(pb.sense, LpObjective::Minimize);
+    assert_eq!(pb.objective.to_string(), "-3 x + 4 y");
+    assert_eq!(pb.objective_offset, 3.);
+}
+
+#[test]
+fn sums() {
+    let mut builder = ProblemBuilder::new("sums");
+    let vars: Vec<_> = (0..3).map(|_| builder.binary("b")).collect();
+    let names: Vec<_> = vars.iter().map(|&v| builder.name(v)).collect();
+    assert_eq!(names, vec!["b", "b2", "b3"]);
+    let weighted: Expr = vars.iter().zip(1..).map(|(&v, w)| v * w as f64).sum();
+    builder.add_constraint(vars.iter().copied().sum::<Expr>().eq(1.));
+    builder.add_constraint(weighted.geq(vars[0] + 1.5));
+    let pb = builder.build();
+    assert_eq!(
+        pb.constraints
+            .iter()
+            .map(|c| (c.lhs.to_string(), c.operator, c.rhs))
+            .collect::<Vec<_>>(),
+        vec![
+            ("b + b2 + b3".to_string(), Ordering::Equal, 1.),
+            ("2 b2 + 3 b3".to_string(), Ordering::Greater, 1.5),
+        ]
+    );
+}
+
+#[test]
+fn constants_move_to_the_right_hand_side() {
+    let mut builder = ProblemBuilder::new("constants");
+    let x = builder.add_variable(Variable::new(
+        "x",
+        VariableKind::Integer,
+        f64::NEG_INFINITY,
+        f64::INFINITY,
+    ));
+    let c = (x + 2.).leq(x * 2. - 1.);
+    assert_eq!(c.rhs, -3.);
+    builder.add_constraint(c);
+    builder.add_constraint(Constraint::new(x + 5., Ordering::Less, 7.));
+    builder.maximize(x);
+    let pb = builder.build();
+    assert_eq!(
+        pb.display_lp().to_string(),
+        "\\ constants
+
+Maximize
+  obj: x
+
+Subject To
+  c0: -x <= -3
+  c1: x <= 2
+
+Bounds
+  x free
+
+Generals
+  x
+
+End
+"
+    );
+}
+
+#[test]
+fn ranges() {
+    let mut builder = ProblemBuilder::new("ranges");
+    let x = builder.continuous("x", 0., 10.);
+    let y = builder.continuous("y", 0., 10.);
+    builder.add_range((x + y + 1.).between(2., 5.).named("window"));
+    builder.add_range(x.between(f64::NEG_INFINITY, 3.));
+    let pb = builder.build();
+    let ranges: Vec<_> = pb
+        .ranges
+        .iter()
+        .map(|r| (r.name.as_deref(), r.lhs.to_string(), r.lower, r.upper))
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![
+            (Some("window"), "x + y".to_string(), 1., 4.),
+            (None, "x".to_string(), f64::NEG_INFINITY, 3.),
+        ]
+    );
+}
+
+#[test]
+fn special_ordered_sets() {
+    let mut builder = ProblemBuilder::new("sos");
+    let vars: Vec<_> = (0..3)
+        .map(|i| builder.continuous(&format!("x{}", i), 0., 1.))
+        .collect();
+    builder
+        .add_sos(
+            SosType::S2,
+            vars.iter().zip(1..).map(|(&v, w)| (v, w as f64)),
+        )
+        .priority = Some(1);
+    let pb = builder.build();
+    assert_eq!(
+        pb.sos,
+        vec![SosConstraint {
+            name: None,
+            kind: SosType::S2,
+            variables: vec![
+                ("x0".to_string(), 1.),
+                ("x1".to_string(), 2.),
+                ("x2".to_string(), 3.)
+            ],
+            priority: Some(1),
+        }]
+    );
+}
+
+#[test]
+fn variable_kinds() {
+    let mut builder = ProblemBuilder::new("kinds");
+    builder.binary("b");
+    builder.integer("i", 0., 5.);
+    builder.semi_continuous("s", 2., 4.);
+    let pb = builder.build();
+    let kinds: Vec<_> = pb.variables.iter().map(|v| v.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            VariableKind::Binary,
+            VariableKind::Integer,
+            VariableKind::SemiContinuous
+        ]
+    );
+    assert_eq!(
+        (pb.variables[2].lower_bound, pb.variables[2].upper_bound),
+        (2., 4.)
+    );
+}
+
+#[test]
+fn indicator_constraints() {
+    let mut builder = ProblemBuilder::new("indicators");
+    let x = builder.continuous("x", 0., 10.);
+    let open = builder.binary("open");
+    builder.add_indicator(open, false, (x + 1.).leq(1.).named("closed"));
+    let pb = builder.build();
+    let indicator = &pb.indicators[0];
+    assert_eq!(
+        (indicator.indicator.as_str(), indicator.value),
+        ("open", false)
+    );
+    assert_eq!(indicator.constraint.lhs.to_string(), "x");
+    assert_eq!(indicator.constraint.rhs, 0.);
+    assert_eq!(indicator.constraint.name.as_deref(), Some("closed"));
+}
+
+#[test]
+fn general_constraints() {
+    let mut builder = ProblemBuilder::new("general");
+    let x = builder.continuous("x", -5., 5.);
+    let a = builder.continuous("a", 0., 5.);
+    let abs = GeneralFunction::Abs(builder.name(x).to_string());
+    builder.add_general_constraint(a, abs).name = Some("abs_x".to_string());
+    let pb = builder.build();
+    assert_eq!(pb.general_constraints[0].resultant, "a");
+    assert_eq!(pb.general_constraints[0].name.as_deref(), Some("abs_x"));
+}
+
+#[test]
+fn multiple_objectives() {
+    let mut builder = ProblemBuilder::new("multi");
+    let x = builder.continuous("x", 0., 1.);
+    let y = builder.continuous("y", 0., 1.);
+    builder.add_objective(Objective::new(x + y * 2., 2).named("cost"));
+    builder.add_objective(Objective::new(x.into(), 1));
+    let pb = builder.build();
+    assert_eq!(pb.objectives[0].expression.to_string(), "x + 2 y");
+    assert_eq!(pb.objectives[1].priority, 1);
+}
+
+#[test]
+fn lazy_constraints_and_user_cuts() {
+    let mut builder = ProblemBuilder::new("lazy");
+    let x = builder.continuous("x", 0., 10.);
+    builder.add_lazy_constraint((x + 1.).leq(5.));
+    builder.add_user_cut(x.geq(1.));
+    let pb = builder.build();
+    assert_eq!(pb.lazy_constraints[0].rhs, 4.);
+    assert_eq!(pb.user_cuts[0].operator, Ordering::Greater);
+}
@@ -0,0 +1,280 @@
+use std::cmp::Ordering;
+
+use lp_solvers::lp_format::{
+    Constraint, GeneralConstraint, GeneralFunction, LpObjective, LpProblem, RangeConstraint,
+    VariableKind,
+};
+use lp_solvers::mps_format::{write_mps, MpsFormat};
+use lp_solvers::problem::{Problem, StrExpression, Variable};
+
+fn variable(name: &str, is_integer: bool, lower_bound: f64, upper_bound: f64) -> Variable {
+    Variable::new(name, is_integer, lower_bound, upper_bound)
+}
+
+fn problem() -> Problem<StrExpression> {
+    Problem::new(
+        "mps_problem",
+        LpObjective::Maximize,
+        StrExpression("3 x + 2 y - 0.5 z + 4".to_string()),
+        vec![
+            variable("x", false, f64::NEG_INFINITY, f64::INFINITY),
+            variable("y", true, 0., 1.),
+            variable("z", true, -2., f64::INFINITY),
+            variable("w", false, f64::NEG_INFINITY, 8.),
+            variable("v", false, 0., -1.),
+            variable("u", false, 1.5, 1.5),
+        ],
+        vec![
+            Constraint::new(StrExpression("x + y + z".to_string()), Ordering::Less, 10.),
+            Constraint::new(
+                StrExpression("x - 2 z + 1".to_string()),
+                Ordering::Greater,
+                -3.,
+            ),
+            Constraint::new(
+                StrExpression("y + w + v".to_string()),
+                Ordering::Equal,
+                123456.7890123,
+            ),
+        ],
+    )
+}
+
+fn mps(pb: &Problem<StrExpression>, format: MpsFormat) -> String {
+    let mut out = Vec::new();
+    write_mps(pb, format, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn fixed_format() {
+    let expected = "NAME          mps_problem
+OBJSENSE
+    MAX
+ROWS
+ N  obj
+ L  c0
+ G  c1
+ E  c2
+COLUMNS
+    x         obj       3              c0        1
+    x         c1        1
+    MARKER    'MARKER'                 'INTORG'
+    y         obj       2              c0        1
+    y         c2        1
+    z         obj       -0.5           c0        1
+    z         c1        -2
+    MARKER    'MARKER'                 'INTEND'
+    w         c2        1
+    v         c2        1
+    u         obj       0
+RHS
+    RHS       obj       -4
+    RHS       c0        10
+    RHS       c1        -4
+    RHS       c2        123456.78901
+BOUNDS
+ FR BND       x
+ BV BND       y
+ LO BND       z         -2
+ PL BND       z
+ MI BND       w
+ UP BND       w         8
+ LO BND       v         0
+ UP BND       v         -1
+ FX BND       u         1.5
+ENDATA
+";
+    assert_eq!(mps(&problem(), MpsFormat::Fixed), expected);
+}
+
+#[test]
+fn free_format() {
+    let expected = "NAME mps_problem
+OBJSENSE
+    MAX
+ROWS
+ N  obj
+ L  c0
+ G  c1
+ E  c2
+COLUMNS
+    x obj 3 c0 1
+    x c1 1
+    MARKER 'MARKER' 'INTORG'
+    y obj 2 c0 1
+    y c2 1
+    z obj -0.5 c0 1
+    z c1 -2
+    MARKER 'MARKER' 'INTEND'
+    w c2 1
+    v c2 1
+    u obj 0
+RHS
+    RHS obj -4
+    RHS c0 10
+    RHS c1 -4
+    RHS c2 123456.7890123
+BOUNDS
+ FR BND x
+ BV BND y
+ LO BND z -2
+ PL BND z
+ MI BND w
+ UP BND w 8
+ LO BND v 0
+ UP BND v -1
+ FX BND u 1.5
+ENDATA
+";
+    assert_eq!(mps(&problem(), MpsFormat::Free), expected);
+
+    let mut offset = problem();
+    offset.objective_offset = 1.5;
+    assert!(mps(&offset, MpsFormat::Free).contains("RHS\n    RHS obj -5.5\n"));
+}
+
+#[test]
+fn tmp_file() {
+    let pb = problem();
+    let file = pb.to_tmp_mps_file(MpsFormat::Free).unwrap();
+    assert_eq!(file.path().extension().unwrap(), "mps");
+    let content = std::fs::read_to_string(file.path()).unwrap();
+    assert_eq!(content, mps(&pb, MpsFormat::Free));
+}
+
+#[test]
+fn ranges() {
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.name = "ranges".to_string();
+    pb.objective = StrExpression("x".to_string());
+    pb.constraints = vec![Constraint::new(
+        StrExpression("x".to_string()),
+        Ordering::Less,
+        4.,
+    )];
+    pb.ranges = vec![
+        RangeConstraint {
+            lhs: StrExpression("x + y + 1".to_string()),
+            lower: -1.,
+            upper: 2.,
+            name: Some("window".to_string()),
+        },
+        RangeConstraint {
+            lhs: StrExpression("y".to_string()),
+            lower: f64::NEG_INFINITY,
+            upper: 3.,
+            name: None,
+        },
+    ];
+    let expected = "NAME ranges
+ROWS
+ N  obj
+ L  c0
+ G  window
+ L  r1
+COLUMNS
+    x obj 1 c0 1
+    x window 1
+    y window 1 r1 1
+RHS
+    RHS c0 4
+    RHS window -2
+    RHS r1 3
+RANGES
+    RNG window 3
+BOUNDS
+ENDATA
+";
+    assert_eq!(mps(&pb, MpsFormat::Free), expected);
+}
+
+#[test]
+fn unrepresentable_problems() {
+    let mut pb = problem();
+    pb.variables[0].name = "a_long_name".to_string();
+    pb.constraints[0].lhs = StrExpression("a_long_name + y".to_string());
+    let mut out = Vec::new();
+    let err = write_mps(&pb, MpsFormat::Fixed, &mut out).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(write_mps(&pb, MpsFormat::Free, &mut out).is_ok());
+
+    pb.general_constraints = vec![GeneralConstraint::new(
+        "x",
+        GeneralFunction::Abs("y".to_string()),
+    )];
+    let err = write_mps(&pb, MpsFormat::Free, &mut out).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    pb.objective = StrExpression("x * y".to_string());
+    let err = write_mps(&pb, MpsFormat::Free, &mut out).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn semi_continuous_columns() {
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.name = "semi".to_string();
+    pb.objective = StrExpression("x + y + z".to_string());
+    pb.variables = vec![
+        Variable {
+            kind: VariableKind::SemiContinuous,
+            ..variable("x", false, 2., 5.)
+        },
+        Variable {
+            kind: VariableKind::SemiInteger,
+            ..variable("y", true, 0., f64::INFINITY)
+        },
+        Variable {
+            kind: VariableKind::Binary,
+            ..variable("z", true, 0., 1.)
+        },
+    ];
+    let expected = "NAME semi
+ROWS
+ N  obj
+COLUMNS
+    x obj 1
+    MARKER 'MARKER' 'INTORG'
+    y obj 1
+    z obj 1
+    MARKER 'MARKER' 'INTEND'
+RHS
+BOUNDS
+ LO BND x 2
+ SC BND x 5
+ SC BND y
+ BV BND z
+ENDATA
+";
+    assert_eq!(mps(&pb, MpsFormat::Free), expected);
+}
+
+#[test]
+fn lazy_constraints_are_ordinary_rows() {
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.name = "lazy".to_string();
+    pb.objective = StrExpression("x".to_string());
+    pb.lazy_constraints = vec![Constraint::new(
+        StrExpression("x + y".to_string()),
+        Ordering::Greater,
+        1.,
+    )];
+    pb.user_cuts =
+        vec![Constraint::new(StrExpression("y".to_string()), Ordering::Less, 2.).named("cut")];
+    let expected = "NAME lazy
+ROWS
+ N  obj
+ G  l0
+ L  cut
+COLUMNS
+    x obj 1 l0 1
+    y l0 1 cut 1
+RHS
+    RHS l0 1
+    RHS cut 2
+BOUNDS
+ENDATA
+";
+    assert_eq!(mps(&pb, MpsFormat::Free), expected);
+}
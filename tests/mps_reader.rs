@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+
+use lp_solvers::lp_format::{
+    Constraint, LpObjective, LpProblem, SosConstraint, SosType, VariableKind,
+};
+use lp_solvers::lp_reader::ParseError;
+use lp_solvers::mps_format::{write_mps, MpsFormat};
+use lp_solvers::mps_reader::{parse_mps, read_mps_file};
+use lp_solvers::problem::{Problem, StrExpression, Variable};
+
+fn variable(name: &str, is_integer: bool, lower_bound: f64, upper_bound: f64) -> Variable {
+    Variable::new(name, is_integer, lower_bound, upper_bound)
+}
+
+#[test]
+fn round_trip() {
+    let mut pb = Problem::new(
+        "round_trip",
+        LpObjective::Maximize,
+        StrExpression("3 x + 2 y - 0.5 z + 4".to_string()),
+        vec![
+            variable("x", false, f64::NEG_INFINITY, f64::INFINITY),
+            Variable {
+                kind: VariableKind::Binary,
+                ..variable("y", true, 0., 1.)
+            },
+            variable("z", true, -2., f64::INFINITY),
+            variable("w", false, f64::NEG_INFINITY, 8.),
+            variable("v", false, 0., -1.),
+            variable("u", false, 1.5, 1.5),
+        ],
+        vec![
+            Constraint::new(StrExpression("x + y + z".to_string()), Ordering::Less, 10.),
+            Constraint::new(StrExpression("x - 2 z".to_string()), Ordering::Greater, -3.)
+                .named("lower"),
+            Constraint::new(
+                StrExpression("y + w + v".to_string()),
+                Ordering::Equal,
+                12.5,
+            ),
+        ],
+    );
+    pb.sos = vec![
+        SosConstraint {
+            name: Some("set".to_string()),
+            kind: SosType::S1,
+            variables: vec![("x".to_string(), 1.), ("w".to_string(), 2.)],
+            priority: Some(2),
+        },
+        SosConstraint {
+            name: None,
+            kind: SosType::S2,
+            variables: vec![("v".to_string(), 1.), ("u".to_string(), 2.5)],
+            priority: None,
+        },
+    ];
+    for format in [MpsFormat::Fixed, MpsFormat::Free] {
+        let mut mps = Vec::new();
+        write_mps(&pb, format, &mut mps).unwrap();
+        let read = parse_mps(std::str::from_utf8(&mps).unwrap(), format).unwrap();
+        assert_eq!(read.display_lp().to_string(), pb.display_lp().to_string());
+        assert_eq!(read.sos[0], pb.sos[0]);
+        assert_eq!(read.sos[1].name.as_deref(), Some("s1"));
+    }
+}
+
+#[test]
+fn fixed_format_names_with_spaces() {
+    let pb = parse_mps(
+        "NAME          fixed problem
+* a comment
+ROWS
+ N  cost
+ G  row one
+COLUMNS
+    x 1       cost      1              row one   2
+RHS
+              row one   4
+BOUNDS
+ UP BOUND     x 1       -5
+ENDATA
+",
+        MpsFormat::Fixed,
+    )
+    .unwrap();
+    assert_eq!(pb.name, "fixed problem");
+    assert_eq!(pb.constraints[0].name.as_deref(), Some("row one"));
+    assert_eq!(pb.objective.to_string(), "x 1");
+    assert_eq!(pb.constraints[0].lhs.to_string(), "2 x 1");
+    assert_eq!(pb.constraints[0].rhs, 4.);
+    // a negative upper bound without a lower bound makes the variable unbounded below
+    assert_eq!(pb.variables[0].lower_bound, f64::NEG_INFINITY);
+    assert_eq!(pb.variables[0].upper_bound, -5.);
+}
+
+#[test]
+fn ranges_and_bounds() {
+    let pb = parse_mps(
+        "NAME ranges
+OBJSENSE MAXIMIZE
+ROWS
+ N  obj
+ N  other
+ E  e_pos
+ E  e_neg
+ L  le
+ G  ge
+COLUMNS
+ MARKER 'MARKER' 'INTORG'
+ a obj 1 e_pos 1
+ b e_neg 1 le 1
+ MARKER 'MARKER' 'INTEND'
+ c ge 1 other 3
+ d ge 1
+RHS
+ e_pos 1 e_neg 2
+ le 3 ge 4
+RANGES
+ rng e_pos 5 e_neg -5
+ le -2 ge 2
+BOUNDS
+ UI BND a 10
+ LI BND c -3
+ BV BND d
+ MI BND b
+ENDATA
+",
+        MpsFormat::Free,
+    )
+    .unwrap();
+    assert_eq!(pb.sense, LpObjective::Maximize);
+    assert!(pb.constraints.is_empty());
+    let ranges: Vec<_> = pb
+        .ranges
+        .iter()
+        .map(|r| {
+            (
+                r.name.as_deref().unwrap(),
+                r.lhs.to_string(),
+                r.lower,
+                r.upper,
+            )
+        })
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![
+            ("e_pos", "a".to_string(), 1., 6.),
+            ("e_neg", "b".to_string(), -3., 2.),
+            ("le", "b".to_string(), 1., 3.),
+            ("ge", "c + d".to_string(), 4., 6.),
+        ]
+    );
+    let variables: Vec<_> = pb
+        .variables
+        .iter()
+        .map(|v| (v.name.as_str(), v.kind, v.lower_bound, v.upper_bound))
+        .collect();
+    assert_eq!(
+        variables,
+        vec![
+            ("a", VariableKind::Integer, 0., 10.),
+            ("b", VariableKind::Integer, f64::NEG_INFINITY, f64::INFINITY),
+            ("c", VariableKind::Integer, -3., f64::INFINITY),
+            ("d", VariableKind::Binary, 0., 1.),
+        ]
+    );
+}
+
+#[test]
+fn errors_have_positions() {
+    let err = |content: &str| parse_mps(content, MpsFormat::Free).unwrap_err();
+    assert_eq!(
+        err("NAME\nROWS\n N obj\nCOLUMNS\n x obj 1 c9 2\nENDATA\n"),
+        ParseError {
+            line: 5,
+            column: 10,
+            message: "unknown row c9".to_string()
+        }
+    );
+    assert_eq!(
+        err("NAME\nROWS\n N obj\nCOLUMNS\n x obj one\nENDATA\n").to_string(),
+        "line 5, column 8: invalid number one"
+    );
+    assert_eq!(
+        err("NAME\nROWS\n N obj\nCOLUMNS\n x obj 1\nBOUNDS\n XX BND x 1\nENDATA\n").column,
+        2
+    );
+    assert_eq!(err("NAME\nROWS\n N obj\n").line, 3);
+    let fixed = parse_mps("ROWS\n N  obj\n X  c1\nENDATA\n", MpsFormat::Fixed).unwrap_err();
+    assert_eq!((fixed.line, fixed.column), (3, 2));
+}
+
+#[test]
+fn read_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("problem.mps");
+    std::fs::write(&path, "NAME\nROWS\n N obj\nCOLUMNS\n x obj 1\nENDATA\n").unwrap();
+    let pb = read_mps_file(&path, MpsFormat::Free).unwrap();
+    assert_eq!(pb.objective.to_string(), "x");
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn read_gzipped_file() {
+    use std::io::Write;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("problem.mps.gz");
+    let file = std::fs::File::create(&path).unwrap();
+    let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    encoder
+        .write_all(b"NAME\nROWS\n N obj\nCOLUMNS\n x obj 1\nENDATA\n")
+        .unwrap();
+    encoder.finish().unwrap();
+    let pb = read_mps_file(&path, MpsFormat::Free).unwrap();
+    assert_eq!(pb.objective.to_string(), "x");
+}
+
+#[test]
+fn semi_continuous_bounds() {
+    let pb = parse_mps(
+        "NAME\nROWS\n N obj\nCOLUMNS\n x obj 1\n MARKER 'MARKER' 'INTORG'\n y obj 1\n MARKER 'MARKER' 'INTEND'\n z obj 1\nBOUNDS\n LO BND x 2\n SC BND x 5\n SC BND y\n SC BND z 0\nENDATA\n",
+        MpsFormat::Free,
+    )
+    .unwrap();
+    let variables: Vec<_> = pb
+        .variables
+        .iter()
+        .map(|v| (v.name.as_str(), v.kind, v.lower_bound, v.upper_bound))
+        .collect();
+    assert_eq!(
+        variables,
+        vec![
+            ("x", VariableKind::SemiContinuous, 2., 5.),
+            ("y", VariableKind::SemiInteger, 0., f64::INFINITY),
+            ("z", VariableKind::SemiContinuous, 0., f64::INFINITY),
+        ]
+    );
+}
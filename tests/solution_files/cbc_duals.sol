@@ -0,0 +1,5 @@
+Optimal - objective value 3.50000000
+      0 c0                   1.5                      2
+      1 c1                   0.5                      0
+      0 x                    1.5                      0
+      1 y                      0                      1
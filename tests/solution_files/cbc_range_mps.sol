@@ -0,0 +1,5 @@
+Optimal - objective value 3.50000000
+      0 c0                   1.5                      2
+      1 r0                   1.5                      0
+      2 x                    1.5                      0
+      3 y                      0                      1
@@ -0,0 +1,4 @@
+# Solution for model lp_solvers
+# Objective value: 3.5
+x 1.5
+y 0
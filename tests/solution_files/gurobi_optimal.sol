@@ -0,0 +1,5 @@
+# Solution for model obj
+# Objective value = -1.7e+02
+a 5
+b 6
+c 0
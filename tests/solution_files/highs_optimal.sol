@@ -0,0 +1,29 @@
+Model status
+Optimal
+
+# Primal solution values
+Feasible
+Objective 3.5
+# Columns 2
+x 1.5
+y 0
+# Rows 2
+c0 1.5
+c1 0.5
+
+# Dual solution values
+Feasible
+# Columns 2
+x 0
+y 1
+# Rows 2
+c0 2
+c1 0
+
+# Basis
+HiGHS v1
+Valid
+# Columns 2
+1 0
+# Rows 2
+0 1
@@ -0,0 +1,16 @@
+Model status
+Time limit reached
+
+# Primal solution values
+Feasible
+Objective 12
+# Columns 1
+n 4
+# Rows 0
+
+# Dual solution values
+None
+
+# Basis
+HiGHS v1
+None
@@ -0,0 +1,14 @@
+NAME                : 
+PROBLEM STATUS      : PRIMAL_INFEASIBLE
+SOLUTION STATUS     : PRIMAL_INFEASIBLE_CER
+OBJECTIVE NAME      : obj
+PRIMAL OBJECTIVE    : 0.00000000e+00
+DUAL OBJECTIVE      : 1.00000000e+00
+
+CONSTRAINTS
+INDEX      NAME                     AT ACTIVITY                 LOWER LIMIT              UPPER LIMIT              DUAL LOWER               DUAL UPPER              
+0          c0                       UL 0.00000000e+00           NONE                     -1.00000000e+00          0.00000000e+00           1.00000000e+00          
+
+VARIABLES
+INDEX      NAME                     AT ACTIVITY                 LOWER LIMIT              UPPER LIMIT              DUAL LOWER               DUAL UPPER              
+0          x                        LL 0.00000000e+00           0.00000000e+00           NONE                     1.00000000e+00           0.00000000e+00          
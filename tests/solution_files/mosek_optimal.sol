@@ -0,0 +1,16 @@
+NAME                : 
+PROBLEM STATUS      : PRIMAL_AND_DUAL_FEASIBLE
+SOLUTION STATUS     : OPTIMAL
+OBJECTIVE NAME      : obj
+PRIMAL OBJECTIVE    : 3.50000000e+00
+DUAL OBJECTIVE      : 3.50000000e+00
+
+CONSTRAINTS
+INDEX      NAME                     AT ACTIVITY                 LOWER LIMIT              UPPER LIMIT              DUAL LOWER               DUAL UPPER              
+0          c0                       UL 1.50000000e+00           NONE                     1.50000000e+00           0.00000000e+00           2.00000000e+00          
+1          c1                       BS 5.00000000e-01           NONE                     2.00000000e+00           0.00000000e+00           0.00000000e+00          
+
+VARIABLES
+INDEX      NAME                     AT ACTIVITY                 LOWER LIMIT              UPPER LIMIT              DUAL LOWER               DUAL UPPER              
+0          x                        BS 1.50000000e+00           0.00000000e+00           NONE                     0.00000000e+00           0.00000000e+00          
+1          y                        LL 0.00000000e+00           0.00000000e+00           NONE                     1.00000000e+00           0.00000000e+00          
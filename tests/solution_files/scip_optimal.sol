@@ -0,0 +1,4 @@
+solution status: optimal solution found
+objective value:                                 -170
+a                                                   5 	(obj:-10)
+b                                                   6 	(obj:-20)
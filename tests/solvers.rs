@@ -1,9 +1,20 @@
 extern crate lp_solvers;
 
+use std::cmp::Ordering;
 use std::path::PathBuf;
 
-use lp_solvers::problem::Problem;
-use lp_solvers::solvers::{CbcSolver, GlpkSolver, Solution, SolverWithSolutionParsing, Status};
+use lp_solvers::lp_format::{
+    Constraint, GeneralConstraint, GeneralFunction, RangeConstraint, SosConstraint, SosType,
+    VariableKind,
+};
+use lp_solvers::mps_format::MpsFormat;
+use lp_solvers::problem::{
+    LinearExpression, Problem, QuadraticExpression, StrExpression, Variable,
+};
+use lp_solvers::solvers::{
+    CbcSolver, GlpkSolver, GurobiSolver, HighsSolver, LpSolveSolver, ProblemFormat, ScipSolver,
+    Solution, SolverProgram, SolverTrait, SolverWithSolutionParsing, Status,
+};
 
 fn sol_file(file: &str) -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -18,10 +29,13 @@ fn cbc_optimal() {
     let Solution {
         status,
         results: mut variables,
+        objective_value,
+        ..
     } = solver
         .read_solution_from_path::<Problem>(&sol_file("cbc_optimal.sol"), None)
         .unwrap();
     assert_eq!(status, Status::Optimal);
+    assert_eq!(objective_value, Some(-170.));
     assert_eq!(variables.remove("a"), Some(5f32));
     assert_eq!(variables.remove("b"), Some(6f32));
     assert_eq!(variables.remove("c"), Some(0f32));
@@ -77,11 +91,13 @@ fn glpk_optimal() {
     let Solution {
         status,
         results: mut variables,
+        objective_value,
         ..
     } = solver
         .read_solution_from_path::<Problem>(&sol_file("glpk_optimal.sol"), None)
         .unwrap();
     assert_eq!(status, Status::Optimal);
+    assert_eq!(objective_value, Some(100.));
     assert_eq!(variables.remove("a"), Some(0f32));
     assert_eq!(variables.remove("b"), Some(5f32));
     assert_eq!(variables.remove("c"), Some(0f32));
@@ -119,3 +135,633 @@ fn glpk_empty_col_bounds() {
     assert_eq!(1.0, *solution.get("a").unwrap());
     assert_eq!(0.0, *solution.get("b").unwrap());
 }
+
+#[test]
+fn gurobi_optimal() {
+    let solution = GurobiSolver::new()
+        .read_solution_from_path::<Problem>(&sol_file("gurobi_optimal.sol"), None)
+        .unwrap();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.objective_value, Some(-170.));
+    assert_eq!(solution.results["a"], 5.);
+}
+
+#[test]
+fn gurobi_json() {
+    let solution = GurobiSolver::new()
+        .read_solution_from_path::<Problem>(&sol_file("gurobi_optimal.json"), None)
+        .unwrap();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.objective_value, Some(3.5));
+    assert_eq!(solution.best_bound, Some(3.5));
+    assert_eq!(solution.results["x"], 1.5);
+    assert_eq!(solution.dual_values["c0"], 2.);
+    assert_eq!(solution.dual_values["c1"], 0.);
+    assert_eq!(solution.reduced_costs["y"], 1.);
+    assert_eq!(solution.slacks["c1"], 1.5);
+
+    // gurobi could not tell an infeasible problem from an unbounded one
+    let solution = GurobiSolver::new()
+        .read_solution_from_path::<Problem>(&sol_file("gurobi_inf_or_unbd.json"), None)
+        .unwrap();
+    assert_eq!(solution.status, Status::NotSolved);
+}
+
+#[test]
+fn cbc_dual_values() {
+    let solution = CbcSolver::new()
+        .read_solution_from_path::<Problem>(&sol_file("cbc_duals.sol"), None)
+        .unwrap();
+    assert_eq!(solution.results.len(), 2);
+    assert_eq!(solution.results["x"], 1.5);
+    assert_eq!(solution.dual_values.len(), 2);
+    assert_eq!(solution.dual_values["c0"], 2.);
+    assert_eq!(solution.reduced_costs.len(), 2);
+    assert_eq!(solution.reduced_costs["y"], 1.);
+    assert_eq!(solution.activities["c0"], 1.5);
+    assert!(!solution.activities.contains_key("x"));
+}
+
+#[test]
+fn cbc_rows_are_counted_from_the_problem() {
+    let constraint =
+        |lhs: &str| Constraint::new(StrExpression(lhs.to_string()), Ordering::Less, 1.5);
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.constraints = vec![constraint("x"), constraint("x - y")];
+    // the indices of the columns follow the ones of the rows, and the file ends with a blank line
+    let solution = CbcSolver::new()
+        .read_solution_from_path(&sol_file("cbc_shared_indices.sol"), Some(&pb))
+        .unwrap();
+    assert_eq!(solution.results.len(), 2);
+    assert_eq!(solution.results["x"], 1.5);
+    assert_eq!(solution.dual_values["c0"], 2.);
+    assert_eq!(solution.activities.len(), 2);
+    assert_eq!(solution.reduced_costs["y"], 1.);
+
+    // without constraints, every line is a column
+    let solution = CbcSolver::new()
+        .read_solution_from_path(
+            &sol_file("cbc_duals.sol"),
+            Some(&Problem::<StrExpression>::default()),
+        )
+        .unwrap();
+    assert!(solution.activities.is_empty());
+    assert_eq!(solution.results["c0"], 1.5);
+    assert_eq!(solution.results["y"], 0.);
+}
+
+#[test]
+fn cbc_rows_are_counted_in_the_problem_format() {
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.constraints = vec![Constraint::new(
+        StrExpression("x".to_string()),
+        Ordering::Less,
+        1.5,
+    )];
+    pb.ranges = vec![RangeConstraint {
+        lhs: StrExpression("x - y".to_string()),
+        lower: 0.5,
+        upper: 2.,
+        name: None,
+    }];
+    // a two-sided range is a single row in the MPS format, and two in the .lp format
+    let solution = CbcSolver::new()
+        .with_problem_format(ProblemFormat::Mps(MpsFormat::Free))
+        .read_solution_from_path(&sol_file("cbc_range_mps.sol"), Some(&pb))
+        .unwrap();
+    assert_eq!(solution.activities.len(), 2);
+    assert_eq!(solution.activities["r0"], 1.5);
+    assert_eq!(solution.dual_values["c0"], 2.);
+    assert_eq!(solution.results.len(), 2);
+    assert_eq!(solution.results["x"], 1.5);
+    assert_eq!(solution.reduced_costs["y"], 1.);
+}
+
+#[test]
+fn glpk_dual_values() {
+    for file in ["glpk_empty_col_bounds.sol", "glpk_long_row_name.sol"] {
+        let solution = GlpkSolver::new()
+            .read_solution_from_path::<Problem>(&sol_file(file), None)
+            .unwrap();
+        assert_eq!(solution.results["a"], 1.);
+        let mut duals: Vec<_> = solution.dual_values.into_iter().collect();
+        duals.sort_by(|a, b| a.0.cmp(&b.0));
+        let c2 = if file.contains("long") {
+            "a_long_constraint_name"
+        } else {
+            "c2"
+        };
+        let mut expected = vec![
+            ("c1".to_string(), 0.),
+            ("c3".to_string(), 1.),
+            (c2.to_string(), -1.),
+        ];
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(duals, expected);
+        // both columns are basic
+        assert_eq!(solution.reduced_costs["b"], 0.);
+    }
+    let optimal = GlpkSolver::new()
+        .read_solution_from_path::<Problem>(&sol_file("glpk_optimal.sol"), None)
+        .unwrap();
+    // integer problems have no marginals
+    assert!(optimal.dual_values.is_empty());
+    assert!(optimal.reduced_costs.is_empty());
+    assert_eq!(optimal.activities["c1"], 6000.);
+    assert_eq!(optimal.activities["c3"], -5.);
+}
+
+#[test]
+fn highs_solution_files() {
+    let read = |file: &str| {
+        HighsSolver::new()
+            .read_solution_from_path::<Problem>(&sol_file(file), None)
+            .unwrap()
+    };
+    let optimal = read("highs_optimal.sol");
+    assert_eq!(optimal.status, Status::Optimal);
+    assert_eq!(optimal.objective_value, Some(3.5));
+    assert_eq!(optimal.results["x"], 1.5);
+    assert_eq!(optimal.activities["c1"], 0.5);
+    assert_eq!(optimal.reduced_costs["y"], 1.);
+    assert_eq!(optimal.dual_values["c0"], 2.);
+
+    let infeasible = read("highs_infeasible.sol");
+    assert_eq!(infeasible.status, Status::Infeasible);
+    assert!(infeasible.results.is_empty());
+
+    let infeasible_or_unbounded = read("highs_infeasible_or_unbounded.sol");
+    assert_eq!(infeasible_or_unbounded.status, Status::NotSolved);
+    assert!(infeasible_or_unbounded.results.is_empty());
+
+    let stopped = read("highs_time_limit.sol");
+    assert_eq!(stopped.status, Status::SubOptimal);
+    assert_eq!(stopped.results["n"], 4.);
+    assert!(stopped.dual_values.is_empty());
+
+    let mut solution = stopped;
+    HighsSolver::new().parse_stdout_objective(
+        b"Set option mip_rel_gap to 0.5\n  Gap 50%\nSolving report\n  Status            Time limit reached\n  Primal bound      12\n  Dual bound        9\n  Gap               25% (tolerance: 0.01%)\nWriting the solution, Gap 7\n",
+        &mut solution,
+    );
+    assert_eq!(solution.best_bound, Some(9.));
+    assert_eq!(solution.mip_gap, Some(0.25));
+}
+
+#[test]
+fn scip_solution_files() {
+    let mut pb: Problem = Problem::default();
+    pb.variables = ["a", "b", "c"]
+        .iter()
+        .map(|name| Variable::new(name, VariableKind::Integer, 0., 10.))
+        .collect();
+    let read = |file: &str| {
+        ScipSolver::new()
+            .read_solution_from_path(&sol_file(file), Some(&pb))
+            .unwrap()
+    };
+    let optimal = read("scip_optimal.sol");
+    assert_eq!(optimal.status, Status::Optimal);
+    assert_eq!(optimal.objective_value, Some(-170.));
+    assert_eq!(optimal.results["a"], 5.);
+    assert_eq!(optimal.results["c"], 0.);
+
+    assert_eq!(read("scip_infeasible.sol").status, Status::Infeasible);
+    assert_eq!(
+        read("scip_infeasible_or_unbounded.sol").status,
+        Status::NotSolved
+    );
+    assert_eq!(read("scip_time_limit.sol").status, Status::NotSolved);
+
+    let mut solution = optimal;
+    ScipSolver::new().parse_stdout_objective(
+        b"SCIP Status        : solving was interrupted [gap limit reached]\nPrimal Bound       : -1.70000000000000e+02 (1 solutions)\nDual Bound         : -1.80000000000000e+02\nGap                : 5.00 %\n",
+        &mut solution,
+    );
+    assert_eq!(solution.best_bound, Some(-180.));
+    assert_eq!(solution.mip_gap, Some(0.05));
+}
+
+#[test]
+fn lp_solve_printouts() {
+    let read = |file: &str| {
+        LpSolveSolver::new()
+            .read_solution_from_path::<Problem>(&sol_file(file), None)
+            .unwrap()
+    };
+    let optimal = read("lp_solve_optimal.txt");
+    assert_eq!(optimal.status, Status::Optimal);
+    assert_eq!(optimal.objective_value, Some(3.5));
+    assert_eq!(optimal.results.len(), 2);
+    assert_eq!(optimal.results["x"], 1.5);
+    assert_eq!(optimal.activities["c1"], 0.5);
+    assert_eq!(optimal.dual_values.len(), 2);
+    assert_eq!(optimal.dual_values["c0"], 2.);
+    assert_eq!(optimal.reduced_costs["y"], 1.);
+
+    assert_eq!(read("lp_solve_infeasible.txt").status, Status::Infeasible);
+
+    // a constraint can have the name of a variable
+    let shared = read("lp_solve_shared_names.txt");
+    assert_eq!(shared.dual_values["x"], 2.);
+    assert_eq!(shared.reduced_costs["x"], 0.);
+    assert_eq!(shared.reduced_costs["y"], 1.);
+    assert!(!shared.dual_values.contains_key("y"));
+}
+
+#[test]
+fn bounds_and_gaps_from_the_output() {
+    let mut solution = Solution::new(Status::SubOptimal, Default::default());
+    CbcSolver::new().parse_stdout_objective(
+        b"Result - Stopped on time limit\n\nObjective value:                100.00000000\nLower bound:                    95.000\nGap:                            0.05\n",
+        &mut solution,
+    );
+    assert_eq!(
+        (solution.best_bound, solution.mip_gap),
+        (Some(95.), Some(0.05))
+    );
+
+    GurobiSolver::new().parse_stdout_objective(
+        b"Best objective 1.000000000000e+02, best bound 9.000000000000e+01, gap 10.0000%\n",
+        &mut solution,
+    );
+    assert_eq!(
+        (solution.best_bound, solution.mip_gap),
+        (Some(90.), Some(0.1))
+    );
+}
+
+#[test]
+fn quadratic_objective_is_unsupported() {
+    let mut objective = QuadraticExpression::default();
+    objective.add_quadratic_term("x", "x", 1.);
+    let mut pb: Problem<QuadraticExpression> = Problem::default();
+    pb.objective = objective;
+    assert_eq!(
+        CbcSolver::new().run(&pb).unwrap_err(),
+        "cbc does not support quadratic objectives"
+    );
+    assert_eq!(
+        GlpkSolver::new().run(&pb).unwrap_err(),
+        "glpsol does not support quadratic objectives"
+    );
+}
+
+#[test]
+fn quadratic_constraints_are_unsupported() {
+    let mut pb: Problem<QuadraticExpression> = Problem::default();
+    pb.constraints = vec![QuadraticExpression::second_order_cone("t", &["x"])];
+    assert_eq!(
+        CbcSolver::new().run(&pb).unwrap_err(),
+        "cbc does not support quadratic constraints"
+    );
+    assert_eq!(
+        GlpkSolver::new().run(&pb).unwrap_err(),
+        "glpsol does not support quadratic constraints"
+    );
+}
+
+#[test]
+fn special_ordered_sets_are_unsupported_by_glpk() {
+    let mut pb: Problem = Problem::default();
+    pb.sos = vec![SosConstraint {
+        name: None,
+        kind: SosType::S1,
+        variables: vec![("x".to_string(), 1.)],
+        priority: None,
+    }];
+    assert_eq!(
+        GlpkSolver::new().run(&pb).unwrap_err(),
+        "glpsol does not support special ordered sets"
+    );
+}
+
+#[test]
+fn semi_continuous_variables_are_unsupported() {
+    let mut pb: Problem = Problem::default();
+    pb.variables = vec![Variable::new("x", VariableKind::SemiContinuous, 2., 5.)];
+    assert_eq!(
+        CbcSolver::new().run(&pb).unwrap_err(),
+        "cbc does not support semi-continuous variables"
+    );
+    assert_eq!(
+        GlpkSolver::new().run(&pb).unwrap_err(),
+        "glpsol does not support semi-continuous variables"
+    );
+}
+
+#[test]
+fn indicator_constraints_need_bounds_without_native_support() {
+    let indicator = |upper_bound: f64| -> Problem {
+        let mut pb: Problem = Problem::default();
+        pb.variables = vec![
+            Variable::new("b", VariableKind::Binary, 0., 1.),
+            Variable::new("x", VariableKind::Continuous, 0., upper_bound),
+        ];
+        pb.indicators = vec![Constraint::new(
+            LinearExpression::new(vec![("x", 1.)], 0.),
+            Ordering::Less,
+            1.,
+        )
+        .when("b", true)];
+        pb
+    };
+    assert_eq!(
+        CbcSolver::new().run(&indicator(f64::INFINITY)).unwrap_err(),
+        "Unable to create cbc problem file: \
+         cannot compute a big-M value for the indicator constraint on b: x is unbounded"
+    );
+    let mut pb = indicator(5.);
+    pb.variables[0].kind = VariableKind::Continuous;
+    assert_eq!(
+        GlpkSolver::new().run(&pb).unwrap_err(),
+        "Unable to create glpsol problem file: indicator variable b must be binary"
+    );
+}
+
+#[test]
+fn general_constraints_are_gurobi_only() {
+    let mut pb: Problem = Problem::default();
+    pb.general_constraints = vec![GeneralConstraint::new(
+        "r",
+        GeneralFunction::Abs("x".to_string()),
+    )];
+    assert_eq!(
+        CbcSolver::new().run(&pb).unwrap_err(),
+        "cbc does not support general constraints"
+    );
+    assert_eq!(
+        GlpkSolver::new().run(&pb).unwrap_err(),
+        "glpsol does not support general constraints"
+    );
+}
+
+/// Tests with a solver program that only copies files
+#[cfg(unix)]
+mod recording_solver {
+    use super::*;
+    use lp_solvers::lp_format::{LpObjective, LpProblem, Objective};
+    use std::ffi::OsString;
+    use std::fs::File;
+    use std::io::Read;
+    use std::path::Path;
+    use std::sync::Mutex;
+
+    /// A solver that copies the problem file it is given, and finds the same solution for any problem
+    #[derive(Default)]
+    struct RecordingSolver {
+        problems: Mutex<Vec<String>>,
+    }
+
+    impl SolverProgram for RecordingSolver {
+        fn command_name(&self) -> &str {
+            "cp"
+        }
+
+        fn arguments(&self, lp_file: &Path, solution_file: &Path) -> Vec<OsString> {
+            vec![lp_file.into(), solution_file.into()]
+        }
+    }
+
+    impl SolverWithSolutionParsing for RecordingSolver {
+        fn read_specific_solution<'a, P: LpProblem<'a>>(
+            &self,
+            mut f: &File,
+            _problem: Option<&'a P>,
+        ) -> Result<Solution, String> {
+            let mut problem = String::new();
+            f.read_to_string(&mut problem).map_err(|e| e.to_string())?;
+            self.problems.lock().unwrap().push(problem);
+            let results = [("x", 1.), ("y", 2.), ("t", 3.), ("__obj_constant", 1.)];
+            let mut solution = Solution::new(
+                Status::Optimal,
+                results.iter().map(|&(n, v)| (n.to_string(), v)).collect(),
+            );
+            solution.activities = vec![("c0".to_string(), 3.)].into_iter().collect();
+            Ok(solution)
+        }
+    }
+
+    #[test]
+    fn multiple_objectives_are_solved_by_priority() {
+        let objective = |expression: &str, priority: i32| {
+            Objective::new(StrExpression(expression.to_string()), priority)
+        };
+        let mut pb: Problem<StrExpression> = Problem::default();
+        pb.sense = LpObjective::Maximize;
+        pb.objectives = vec![
+            Objective {
+                abs_tol: 0.5,
+                ..objective("x + y", 2)
+            },
+            objective("t", 1),
+            Objective {
+                weight: 2.,
+                ..objective("x", 1)
+            },
+        ];
+        pb.objective_offset = 0.5;
+        let solver = RecordingSolver::default();
+        let solution = solver.run(&pb).unwrap();
+        assert_eq!(solution.status, Status::Optimal);
+        // the value of x + y, not of the objective of the last solve
+        assert_eq!(solution.objective_value, Some(3.5));
+        let problems = solver.problems.into_inner().unwrap();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0]
+            .contains("Maximize\n  obj: x + y + 0.5 __obj_constant\n\nSubject To\n\nBounds"));
+        assert!(problems[1].contains(
+            "Maximize\n  obj: t + 2 x + 0.5 __obj_constant\n\nSubject To\n  c0: x + y >= 2.5\n"
+        ));
+    }
+
+    #[test]
+    fn objective_constants_use_a_fixed_variable() {
+        let mut pb: Problem<StrExpression> = Problem::default();
+        pb.objective = StrExpression("x + 1".to_string());
+        pb.objective_offset = 2.;
+        let solver = RecordingSolver::default();
+        let solution = solver.run(&pb).unwrap();
+        assert!(!solution.results.contains_key("__obj_constant"));
+        let problems = solver.problems.into_inner().unwrap();
+        assert!(problems[0].contains("obj: x + 3 __obj_constant\n"));
+    }
+
+    #[test]
+    fn slacks_are_deduced_from_activities() {
+        let mut pb: Problem<StrExpression> = Problem::default();
+        pb.objective = StrExpression("x".to_string());
+        pb.constraints = vec![Constraint::new(
+            StrExpression("x + y + 1".to_string()),
+            Ordering::Less,
+            5.,
+        )];
+        let solution = RecordingSolver::default().run(&pb).unwrap();
+        assert_eq!(solution.activities["c0"], 3.);
+        assert_eq!(solution.slacks["c0"], 1.);
+    }
+}
+
+#[cfg(feature = "xpress")]
+#[test]
+fn xpress_names_do_not_change_the_status() {
+    use lp_solvers::solvers::XpressSolver;
+
+    let solution = XpressSolver::default()
+        .read_solution_from_path::<Problem>(&sol_file("xpress_infeasible_names.prt"), None)
+        .unwrap();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.objective_value, Some(3.5));
+    assert_eq!(solution.results["unbounded_cost"], 0.);
+    assert_eq!(solution.dual_values["infeasible_c0"], 2.);
+}
+
+#[cfg(all(feature = "xpress", unix))]
+#[test]
+fn xpress_with_a_stub_optimizer() {
+    use lp_solvers::solvers::XpressSolver;
+    use std::os::unix::fs::PermissionsExt;
+
+    // copies a recorded solution to the file given to WRITEPRTSOL
+    let dir = tempfile::tempdir().unwrap();
+    let stub = dir.path().join("optimizer");
+    let script = format!(
+        "#!/bin/sh
+for arg in \"$@\"; do
+  case \"$arg\" in
+    WRITEPRTSOL*)
+      path=\"${{arg#WRITEPRTSOL \\\"}}\"
+      cp '{}' \"${{path%\\\"}}\"
+      ;;
+  esac
+done
+",
+        sol_file("xpress_optimal.prt").display()
+    );
+    std::fs::write(&stub, script).unwrap();
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut pb: Problem<StrExpression> = Problem::default();
+    pb.objective = StrExpression("x + 2 y".to_string());
+    pb.constraints = vec![Constraint::new(
+        StrExpression("x".to_string()),
+        Ordering::Less,
+        1.5,
+    )];
+    let solver = XpressSolver::with_command(stub.to_str().unwrap().to_string());
+    let solution = solver.run(&pb).unwrap();
+    assert_eq!(solution.status, Status::Optimal);
+    assert_eq!(solution.objective_value, Some(3.5));
+    assert_eq!(solution.results["x"], 1.5);
+    assert_eq!(solution.reduced_costs["y"], 1.);
+    assert_eq!(solution.dual_values["c0"], 2.);
+    assert_eq!(solution.slacks["c1"], -0.5);
+    assert!(!solution.dual_values.contains_key("obj"));
+}
+
+#[cfg(feature = "copt")]
+#[test]
+fn copt_solution_file() {
+    use lp_solvers::solvers::CoptSolver;
+
+    let solver = CoptSolver::default();
+    let mut solution = solver
+        .read_solution_from_path::<Problem>(&sol_file("copt_optimal.sol"), None)
+        .unwrap();
+    assert_eq!(solution.objective_value, Some(3.5));
+    assert_eq!(solution.results["x"], 1.5);
+    assert_eq!(solution.results["y"], 0.);
+
+    let stdout = b"Solving finished\nStatus: Time limit reached\nBest solution   : 4.0000000000e+00\nBest bound      : 3.0000000000e+00\nBest gap        : 25.0000%\n";
+    assert_eq!(solver.parse_stdout_status(stdout), Some(Status::SubOptimal));
+    assert_eq!(
+        solver.parse_stdout_status(b"Status: Infeasible"),
+        Some(Status::Infeasible)
+    );
+    assert_eq!(
+        solver.parse_stdout_status(
+            b"Status: Time limit reached\nBest solution   : --\nBest bound      : 3.0000000000e+00\n"
+        ),
+        Some(Status::NotSolved)
+    );
+    let missing = solver
+        .read_solution_from_path::<Problem>(&sol_file("copt_missing.sol"), None)
+        .unwrap();
+    assert_eq!(missing.status, Status::NotSolved);
+    assert!(missing.results.is_empty());
+    solver.parse_stdout_objective(stdout, &mut solution);
+    assert_eq!(solution.best_bound, Some(3.));
+    assert_eq!(solution.mip_gap, Some(0.25));
+}
+
+#[cfg(feature = "mosek")]
+#[test]
+fn mosek_solution_files() {
+    use lp_solvers::solvers::MosekSolver;
+
+    let read = |file: &str| {
+        MosekSolver::default()
+            .read_solution_from_path::<Problem>(&sol_file(file), None)
+            .unwrap()
+    };
+    let optimal = read("mosek_optimal.sol");
+    assert_eq!(optimal.status, Status::Optimal);
+    assert_eq!(optimal.objective_value, Some(3.5));
+    assert_eq!(optimal.results["x"], 1.5);
+    assert_eq!(optimal.activities["c1"], 0.5);
+    assert_eq!(optimal.reduced_costs["y"], 1.);
+    assert_eq!(optimal.dual_values["c0"], 2.);
+
+    let infeasible = read("mosek_infeasible.sol");
+    assert_eq!(infeasible.status, Status::Infeasible);
+    assert!(infeasible.results.is_empty());
+
+    let integer = read("mosek_integer.int");
+    assert_eq!(integer.status, Status::SubOptimal);
+    assert_eq!(integer.objective_value, Some(12.));
+    assert_eq!(integer.results["n"], 4.);
+    assert!(integer.dual_values.is_empty());
+}
+
+#[cfg(feature = "mosek")]
+#[test]
+fn mosek_reads_the_solution_of_the_problem_type() {
+    use lp_solvers::solvers::MosekSolver;
+
+    // the integer solution is written to the solution file, the others next to it
+    let dir = tempfile::tempdir().unwrap();
+    let solution_file = dir.path().join("result.sol");
+    let copy = |fixture: &str, extension: &str| {
+        let mut path = solution_file.clone().into_os_string();
+        path.push(extension);
+        std::fs::copy(sol_file(fixture), path).unwrap();
+    };
+    copy("mosek_optimal.sol", ".bas");
+    copy("mosek_integer.int", ".itr");
+    copy("mosek_integer.int", "");
+
+    let variable = |kind: VariableKind| Variable::new("n", kind, 0., 10.);
+    let mut continuous: Problem = Problem::default();
+    continuous.variables = vec![variable(VariableKind::Continuous)];
+    let mut integer: Problem = Problem::default();
+    integer.variables = vec![variable(VariableKind::Integer)];
+    let read = |pb: &Problem| {
+        MosekSolver::default()
+            .read_solution_from_path(&solution_file, Some(pb))
+            .unwrap()
+            .objective_value
+    };
+    // the basic solution is preferred to the interior point one
+    assert_eq!(read(&continuous), Some(3.5));
+    assert_eq!(read(&integer), Some(12.));
+    std::fs::remove_file(dir.path().join("result.sol.bas")).unwrap();
+    assert_eq!(read(&continuous), Some(12.));
+
+    // a limit was reached before an integer solution was found
+    std::fs::remove_file(&solution_file).unwrap();
+    let stopped = MosekSolver::default()
+        .read_solution_from_path(&solution_file, Some(&integer))
+        .unwrap();
+    assert_eq!(stopped.status, Status::NotSolved);
+    assert!(stopped.results.is_empty());
+}